@@ -12,7 +12,7 @@ use zerodao::{
     opaque::SessionKeys, AccountId, BabeConfig, Balance, BalancesConfig, BlockNumber,
     CouncilConfig, ElectionsPhragmenConfig, CurrencyId, GenesisConfig, GrandpaConfig, ImOnlineConfig, SessionConfig,
     Signature, StakerStatus, StakingConfig, SudoConfig, SystemConfig, TechnicalCommitteeConfig,
-    TokensConfig, ZdReputationConfig, WASM_BINARY,
+    TokensConfig, ZdReputationConfig, ZdSeedsConfig, WASM_BINARY,
 };
 
 // The URL for the telemetry server.
@@ -111,6 +111,12 @@ pub fn development_config() -> Result<ChainSpec, String> {
                         10000u128.pow(12),
                     ),
                 ],
+                // Initial seeds
+                vec![
+                    get_account_id_from_seed::<sr25519::Public>("Alice"),
+                    get_account_id_from_seed::<sr25519::Public>("Bob"),
+                    get_account_id_from_seed::<sr25519::Public>("Charlie"),
+                ],
                 true,
             )
         },
@@ -199,6 +205,15 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
                         50000000u128.pow(12),
                     ),
                 ],
+                // Initial seeds
+                vec![
+                    get_account_id_from_seed::<sr25519::Public>("Alice"),
+                    get_account_id_from_seed::<sr25519::Public>("Bob"),
+                    get_account_id_from_seed::<sr25519::Public>("Charlie"),
+                    get_account_id_from_seed::<sr25519::Public>("Dave"),
+                    get_account_id_from_seed::<sr25519::Public>("Eve"),
+                    get_account_id_from_seed::<sr25519::Public>("Ferdie"),
+                ],
                 true,
             )
         },
@@ -229,6 +244,7 @@ fn testnet_genesis(
     period: BlockNumber,
     root_key: AccountId,
     endowed_accounts: Vec<(AccountId, u128)>,
+    seeds: Vec<AccountId>,
     enable_println: bool,
 ) -> GenesisConfig {
     const STASH: Balance = 20_000;
@@ -297,6 +313,8 @@ fn testnet_genesis(
             },
         }),
         zd_reputation: Some(ZdReputationConfig { period }),
+        zd_seeds: Some(ZdSeedsConfig { seeds }),
+        zd_tokens: Some(Default::default()),
         orml_tokens: Some(TokensConfig {
             endowed_accounts: vec![],
         }),
@@ -7,6 +7,8 @@ mod service;
 mod cli;
 mod command;
 mod rpc;
+mod reputation_rpc;
+mod tir;
 
 fn main() -> sc_cli::Result<()> {
 	command::run()
@@ -0,0 +1,162 @@
+//! RPC subscription to the reputation thresholds crossed by a set of accounts.
+//!
+//! `zd_reputation` deposits `ThresholdCrossed` when a finalised reputation
+//! moves across a watched threshold. Subscribers receive, for every imported
+//! block with any, the crossings of the accounts they asked for.
+
+use std::{collections::BTreeSet, sync::Arc};
+
+use futures::{future, StreamExt, TryStreamExt};
+use jsonrpc_core::{
+	futures::{Future, Sink},
+	Error, ErrorCode,
+};
+use jsonrpc_derive::rpc;
+use jsonrpc_pubsub::{manager::SubscriptionManager, typed::Subscriber, SubscriptionId};
+use sc_client_api::BlockchainEvents;
+use sc_rpc::SubscriptionTaskExecutor;
+use serde::{Deserialize, Serialize};
+use zerodao::{opaque::Block, AccountId, Hash};
+
+use crate::tir::{reputation_events, ReputationEvent};
+
+/// A finalised reputation crossing a watched threshold.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThresholdCrossing {
+	/// Block in which the crossing was reported.
+	pub block: Hash,
+	/// Account whose reputation crossed the threshold.
+	pub who: AccountId,
+	/// The threshold crossed.
+	pub threshold: u32,
+	/// Reputation before the round.
+	pub old: u32,
+	/// Reputation finalised in the round.
+	pub new: u32,
+}
+
+/// Reputation threshold RPC methods.
+#[rpc]
+pub trait ReputationThresholdApi {
+	/// RPC metadata
+	type Metadata;
+
+	/// Subscribe to the threshold crossings of `accounts`.
+	#[pubsub(
+		subscription = "reputation_thresholdCrossings",
+		subscribe,
+		name = "reputation_subscribeThresholdCrossings"
+	)]
+	fn subscribe_threshold_crossings(
+		&self,
+		metadata: Self::Metadata,
+		subscriber: Subscriber<Vec<ThresholdCrossing>>,
+		accounts: Vec<AccountId>,
+	);
+
+	/// Unsubscribe from threshold crossings.
+	#[pubsub(
+		subscription = "reputation_thresholdCrossings",
+		unsubscribe,
+		name = "reputation_unsubscribeThresholdCrossings"
+	)]
+	fn unsubscribe_threshold_crossings(
+		&self,
+		metadata: Option<Self::Metadata>,
+		id: SubscriptionId,
+	) -> jsonrpc_core::Result<bool>;
+}
+
+/// Implements the `ReputationThresholdApi` over the events of imported blocks.
+pub struct ReputationThreshold<C> {
+	client: Arc<C>,
+	manager: SubscriptionManager,
+}
+
+impl<C> ReputationThreshold<C> {
+	/// Create a new `ReputationThreshold` with the given reference to the client.
+	pub fn new(client: Arc<C>, executor: SubscriptionTaskExecutor) -> Self {
+		Self {
+			client,
+			manager: SubscriptionManager::new(Arc::new(executor)),
+		}
+	}
+}
+
+/// Returns the crossings of `accounts` among `events`.
+pub fn crossings_of(
+	accounts: &BTreeSet<AccountId>,
+	block: Hash,
+	events: Vec<ReputationEvent>,
+) -> Vec<ThresholdCrossing> {
+	events
+		.into_iter()
+		.filter_map(|event| match event {
+			ReputationEvent::ThresholdCrossed(who, threshold, old, new)
+				if accounts.contains(&who) =>
+			{
+				Some(ThresholdCrossing {
+					block,
+					who,
+					threshold,
+					old,
+					new,
+				})
+			}
+			_ => None,
+		})
+		.collect()
+}
+
+impl<C> ReputationThresholdApi for ReputationThreshold<C>
+where
+	C: BlockchainEvents<Block> + Send + Sync + 'static,
+{
+	type Metadata = sc_rpc::Metadata;
+
+	fn subscribe_threshold_crossings(
+		&self,
+		_metadata: Self::Metadata,
+		subscriber: Subscriber<Vec<ThresholdCrossing>>,
+		accounts: Vec<AccountId>,
+	) {
+		let events = match reputation_events(self.client.clone()) {
+			Ok(events) => events,
+			Err(err) => {
+				let _ = subscriber.reject(Error {
+					code: ErrorCode::InternalError,
+					message: format!("Unable to follow reputation events: {:?}", err),
+					data: None,
+				});
+				return;
+			}
+		};
+		let accounts = accounts.into_iter().collect::<BTreeSet<_>>();
+		let stream = events
+			.filter_map(move |(block, events)| {
+				let crossings = crossings_of(&accounts, block, events);
+				future::ready(match crossings.is_empty() {
+					true => None,
+					false => Some(crossings),
+				})
+			})
+			.map(|crossings| Ok::<_, ()>(Ok(crossings)))
+			.boxed()
+			.compat();
+
+		self.manager.add(subscriber, |sink| {
+			sink.sink_map_err(|err| log::warn!("Error sending threshold crossings: {:?}", err))
+				.send_all(stream)
+				.map(|_| ())
+		});
+	}
+
+	fn unsubscribe_threshold_crossings(
+		&self,
+		_metadata: Option<Self::Metadata>,
+		id: SubscriptionId,
+	) -> jsonrpc_core::Result<bool> {
+		Ok(self.manager.cancel(id))
+	}
+}
@@ -0,0 +1,54 @@
+//! Helpers for following the TIR reputation system from the node.
+//!
+//! `zd_reputation` deposits `RoundStarted`, `StepChanged` and `RoundFinalized`
+//! whenever the system changes state. These helpers decode them from the
+//! `System::Events` storage of every imported block, so that node-side
+//! services can react to them instead of polling `SystemInfo`.
+
+use std::sync::Arc;
+
+use codec::Decode;
+use futures::{future, Stream, StreamExt};
+use sc_client_api::BlockchainEvents;
+use sp_core::{hashing::twox_128, storage::StorageKey};
+use zerodao::{opaque::Block, Event, Hash, Runtime};
+
+/// Events deposited by `zd_reputation`.
+pub type ReputationEvent = zd_reputation::Event<Runtime>;
+
+type EventRecords = Vec<frame_system::EventRecord<Event, Hash>>;
+
+/// Storage key of `System::Events`.
+pub fn system_events_key() -> StorageKey {
+	let mut key = twox_128(b"System").to_vec();
+	key.extend_from_slice(&twox_128(b"Events"));
+	StorageKey(key)
+}
+
+/// Returns a stream of the `zd_reputation` events of each imported block, blocks
+/// without any are skipped.
+pub fn reputation_events<C>(
+	client: Arc<C>,
+) -> sp_blockchain::Result<impl Stream<Item = (Hash, Vec<ReputationEvent>)>>
+where
+	C: BlockchainEvents<Block>,
+{
+	let stream = client.storage_changes_notification_stream(Some(&[system_events_key()]), None)?;
+
+	Ok(stream.filter_map(|(hash, changes)| {
+		let events = changes
+			.iter()
+			.filter_map(|(_, _, data)| data)
+			.filter_map(|data| EventRecords::decode(&mut &data.0[..]).ok())
+			.flatten()
+			.filter_map(|record| match record.event {
+				Event::zd_reputation(event) => Some(event),
+				_ => None,
+			})
+			.collect::<Vec<_>>();
+		future::ready(match events.is_empty() {
+			true => None,
+			false => Some((hash, events)),
+		})
+	}))
+}
@@ -34,6 +34,7 @@ use frame_support::{ensure, pallet, transactional, traits::Get};
 use frame_system::{self as system};
 use sp_runtime::{traits::Zero, DispatchError, DispatchResult, SaturatedConversion};
 
+use sp_std::{vec, vec::Vec};
 use zd_primitives::{fee::SweeperFee, AppId, Balance, ChallengeStatus, Metadata, TIRStep, Pool};
 use zd_support::{ChallengeBase, MultiBaseToken, Reputation};
 
@@ -46,6 +47,8 @@ pub use pallet::*;
 
 /// Maximum number of data uploads, this is a setting for security reasons.
 const MAX_UPDATE_COUNT: u32 = 257;
+/// Maximum number of stakes in a challenge.
+pub const MAX_STAKERS: usize = 64;
 
 #[pallet]
 pub mod pallet {
@@ -91,6 +94,20 @@ pub mod pallet {
     #[pallet::getter(fn last_at)]
     pub type LastAt<T: Config> = StorageMap<_, Twox64Concat, AppId, T::BlockNumber, ValueQuery>;
 
+    /// The challengers who have staked in the challenge against `target`, with their stakes,
+    /// at most `MAX_STAKERS`.
+    #[pallet::storage]
+    #[pallet::getter(fn stakers_of)]
+    pub type Stakers<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        AppId,
+        Twox64Concat,
+        T::AccountId,
+        Vec<(T::AccountId, Balance)>,
+        ValueQuery,
+    >;
+
     #[pallet::event]
     #[pallet::metadata(T::AccountId = "AccountId")]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
@@ -119,6 +136,8 @@ pub mod pallet {
         NonExistent,
         /// Too many uploads
         TooMany,
+        /// Too many stakes in the challenge, and the new one is not larger than the smallest
+        TooManyStakers,
         /// An error in progress has occurred
         ProgressErr,
         /// Status does not match
@@ -154,8 +173,122 @@ impl<T: Config> Pallet<T> {
         T::ChallengeStakingAmount::get()
     }
 
-    pub(crate) fn staking(who: &T::AccountId, amount: Balance) -> DispatchResult {
-        T::ZdToken::staking(who, &amount)
+    /// `who` stakes `amount` in the challenge against `target`, returning the stake
+    /// evicted to make room for it, if any.
+    pub(crate) fn staking(
+        who: &T::AccountId,
+        app_id: &AppId,
+        target: &T::AccountId,
+        amount: Balance,
+    ) -> Result<Balance, DispatchError> {
+        let evicted = Self::add_staker(who, app_id, target, amount)?;
+        T::ZdToken::stake(who, &(*app_id, target.clone()), &amount)?;
+        Ok(evicted)
+    }
+
+    /// Records the stake of `who` in the challenge against `target`, returning the stake
+    /// evicted to make room for it, if any.
+    ///
+    /// Once there are `MAX_STAKERS`, a stake only gets a place if it is larger than the
+    /// smallest one, which is returned to its staker.
+    pub(crate) fn add_staker(
+        who: &T::AccountId,
+        app_id: &AppId,
+        target: &T::AccountId,
+        amount: Balance,
+    ) -> Result<Balance, DispatchError> {
+        Stakers::<T>::try_mutate(app_id, target, |stakers| -> Result<Balance, DispatchError> {
+            let mut evicted: Balance = Zero::zero();
+            if stakers.len() >= MAX_STAKERS {
+                let (index, smallest) = stakers
+                    .iter()
+                    .map(|(_, stake)| *stake)
+                    .enumerate()
+                    .min_by_key(|(_, stake)| *stake)
+                    .ok_or(Error::<T>::TooManyStakers)?;
+                ensure!(amount > smallest, Error::<T>::TooManyStakers);
+                let (staker, stake) = stakers.remove(index);
+                T::ZdToken::unstake(&staker, &(*app_id, target.clone()), &stake)?;
+                evicted = stake;
+            }
+            stakers.push((who.clone(), amount));
+            Ok(evicted)
+        })
+    }
+
+    /// Settles the stakes in the challenge against `target`, of which `awards` are due.
+    ///
+    /// A staker who is due an award gets their stake back up to that award, the rest is
+    /// forfeited to pay the others. Each award is reduced by the stake returned to it.
+    pub(crate) fn settle_stakes(
+        app_id: &AppId,
+        target: &T::AccountId,
+        awards: &mut [(T::AccountId, Balance)],
+    ) -> DispatchResult {
+        let staking_id = (*app_id, target.clone());
+        for (who, amount) in Stakers::<T>::take(app_id, target) {
+            let returned = match awards.iter_mut().find(|(winner, _)| *winner == who) {
+                Some((_, award)) => {
+                    let returned = amount.min(*award);
+                    *award -= returned;
+                    returned
+                }
+                None => Zero::zero(),
+            };
+            if !returned.is_zero() {
+                T::ZdToken::unstake(&who, &staking_id, &returned)?;
+            }
+            let forfeited = amount - returned;
+            if !forfeited.is_zero() {
+                T::ZdToken::forfeit(&who, &staking_id, &forfeited)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Releases `awards` after settling the stakes in the challenge against `target`.
+    pub(crate) fn pay_awards(
+        app_id: &AppId,
+        target: &T::AccountId,
+        mut awards: Vec<(T::AccountId, Balance)>,
+    ) -> DispatchResult {
+        Self::settle_stakes(app_id, target, &mut awards)?;
+        for (who, award) in awards {
+            if award > 0 {
+                Self::release(&who, award)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Pays `amount` to `who` while the challenge against `target` goes on.
+    ///
+    /// The stakes of `who` are returned first, up to `amount`, and the rest is released.
+    /// The other stakes are left to be settled when the challenge ends.
+    pub(crate) fn pay_running(
+        app_id: &AppId,
+        target: &T::AccountId,
+        who: &T::AccountId,
+        amount: Balance,
+    ) -> DispatchResult {
+        let staking_id = (*app_id, target.clone());
+        let mut remaining = amount;
+        Stakers::<T>::try_mutate(app_id, target, |stakers| -> DispatchResult {
+            for (_, stake) in stakers.iter_mut().filter(|(staker, _)| staker == who) {
+                let returned = (*stake).min(remaining);
+                if !returned.is_zero() {
+                    T::ZdToken::unstake(who, &staking_id, &returned)?;
+                    *stake -= returned;
+                    remaining -= returned;
+                }
+            }
+            stakers.retain(|(_, stake)| !stake.is_zero());
+            Ok(())
+        })?;
+        if !remaining.is_zero() {
+            Self::release(who, remaining)?;
+        }
+        Ok(())
     }
 
     pub(crate) fn release(who: &T::AccountId, amount: Balance) -> DispatchResult {
@@ -188,6 +321,8 @@ impl<T: Config> Pallet<T> {
     }
 
     pub(crate) fn do_settle(
+        app_id: &AppId,
+        target: &T::AccountId,
         challenge: &mut Metadata<T::AccountId, T::BlockNumber>,
         restart: &bool,
         joint_benefits: &bool,
@@ -202,7 +337,7 @@ impl<T: Config> Pallet<T> {
                         .checked_div(2)
                         .ok_or(Error::<T>::Overflow)?;
                     challenge.pool.staking -= arbitral_fee;
-                    Self::release(&challenge.challenger, arbitral_fee)?;
+                    Self::pay_running(app_id, target, &challenge.challenger, arbitral_fee)?;
                 }
                 challenge.restart(!joint_benefits);
                 Ok(())
@@ -229,47 +364,12 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
-    pub(crate) fn after_upload(app_id: &AppId) {
-        <LastAt<T>>::mutate(*app_id, |l| *l = Self::now());
-    }
-
-    pub(crate) fn is_challenge_timeout(last_update: &T::BlockNumber) -> bool {
-        let now_block_number = system::Module::<T>::block_number();
-        now_block_number > (Self::get_challenge_timeout() + *last_update)
-    }
-}
-
-impl<T: Config> ChallengeBase<T::AccountId, AppId, Balance, T::BlockNumber> for Pallet<T> {
-    fn set_metadata(
-        app_id: &AppId,
-        target: &T::AccountId,
-        metadata: &Metadata<T::AccountId, T::BlockNumber>,
-    ) {
-        <Metadatas<T>>::mutate(*app_id, target, |m| *m = metadata.clone());
-    }
-
-    fn is_all_harvest(app_id: &AppId) -> bool {
-        <Metadatas<T>>::iter_prefix_values(app_id).next().is_none()
-    }
-
-    fn is_all_timeout(app_id: &AppId, now: &T::BlockNumber) -> bool {
-        let last = LastAt::<T>::get(app_id);
-        *now > last + Self::get_challenge_timeout()
-    }
-
-    fn set_status(app_id: &AppId, target: &T::AccountId, status: &ChallengeStatus) {
-        <Metadatas<T>>::mutate(app_id, target, |c| c.set_status(status));
-    }
-
-    #[transactional]
-    fn harvest(
-        who: &T::AccountId,
-        app_id: &AppId,
-        target: &T::AccountId,
-    ) -> Result<Option<u64>, DispatchError> {
-        let challenge = Self::get_metadata_exist(app_id, target)?;
-        let total_amount: Balance = challenge.total_amount().ok_or(Error::<T>::Overflow)?;
-        let (sweeper_fee, awards) = Self::checked_sweeper_fee(&challenge, who, &total_amount)?;
+    /// Splits `awards` between the pathfinder and the challenger of `challenge`
+    /// according to its status, along with the score to be recorded, if any.
+    pub(crate) fn split_awards(
+        challenge: &Metadata<T::AccountId, T::BlockNumber>,
+        awards: Balance,
+    ) -> (Balance, Balance, Option<u64>) {
         let mut pathfinder_amount: Balance = Zero::zero();
         let mut challenger_amount: Balance = Zero::zero();
         let mut maybe_score: Option<u64> = None;
@@ -312,19 +412,84 @@ impl<T: Config> ChallengeBase<T::AccountId, AppId, Balance, T::BlockNumber> for
                 }
             },
         }
-        if sweeper_fee > 0 {
-            Self::release(who, sweeper_fee)?;
-        }
-        if pathfinder_amount > 0 {
-            Self::release(&challenge.pathfinder, pathfinder_amount)?;
-        }
-        if challenger_amount > 0 {
-            Self::release(&challenge.challenger, challenger_amount)?;
-        };
+        (pathfinder_amount, challenger_amount, maybe_score)
+    }
+
+    pub(crate) fn after_upload(app_id: &AppId) {
+        <LastAt<T>>::mutate(*app_id, |l| *l = Self::now());
+    }
+
+    pub(crate) fn is_challenge_timeout(last_update: &T::BlockNumber) -> bool {
+        let now_block_number = system::Module::<T>::block_number();
+        now_block_number > (Self::get_challenge_timeout() + *last_update)
+    }
+}
+
+impl<T: Config> ChallengeBase<T::AccountId, AppId, Balance, T::BlockNumber> for Pallet<T> {
+    fn set_metadata(
+        app_id: &AppId,
+        target: &T::AccountId,
+        metadata: &Metadata<T::AccountId, T::BlockNumber>,
+    ) {
+        <Metadatas<T>>::mutate(*app_id, target, |m| *m = metadata.clone());
+    }
+
+    fn is_all_harvest(app_id: &AppId) -> bool {
+        <Metadatas<T>>::iter_prefix_values(app_id).next().is_none()
+    }
+
+    fn is_all_timeout(app_id: &AppId, now: &T::BlockNumber) -> bool {
+        let last = LastAt::<T>::get(app_id);
+        *now > last + Self::get_challenge_timeout()
+    }
+
+    fn set_status(app_id: &AppId, target: &T::AccountId, status: &ChallengeStatus) {
+        <Metadatas<T>>::mutate(app_id, target, |c| c.set_status(status));
+    }
+
+    #[transactional]
+    fn harvest(
+        who: &T::AccountId,
+        app_id: &AppId,
+        target: &T::AccountId,
+    ) -> Result<Option<u64>, DispatchError> {
+        let challenge = Self::get_metadata_exist(app_id, target)?;
+        let total_amount: Balance = challenge.total_amount().ok_or(Error::<T>::Overflow)?;
+        let (sweeper_fee, awards) = Self::checked_sweeper_fee(&challenge, who, &total_amount)?;
+        let (pathfinder_amount, challenger_amount, maybe_score) =
+            Self::split_awards(&challenge, awards);
+        Self::pay_awards(
+            app_id,
+            target,
+            vec![
+                (challenge.pathfinder, pathfinder_amount),
+                (challenge.challenger, challenger_amount),
+                (who.clone(), sweeper_fee),
+            ],
+        )?;
         Self::remove(app_id, target);
         Ok(maybe_score)
     }
 
+    #[transactional]
+    fn settle_all(app_id: &AppId) -> DispatchResult {
+        for (target, challenge) in Metadatas::<T>::drain_prefix(app_id) {
+            let total_amount = challenge.total_amount().ok_or(Error::<T>::Overflow)?;
+            let (pathfinder_amount, challenger_amount, _) =
+                Self::split_awards(&challenge, total_amount);
+            Self::pay_awards(
+                app_id,
+                &target,
+                vec![
+                    (challenge.pathfinder, pathfinder_amount),
+                    (challenge.challenger, challenger_amount),
+                ],
+            )?;
+        }
+        LastAt::<T>::remove(app_id);
+        Ok(())
+    }
+
     #[transactional]
     fn launch(
         app_id: &AppId,
@@ -358,10 +523,25 @@ impl<T: Config> ChallengeBase<T::AccountId, AppId, Balance, T::BlockNumber> for
             .ok_or(Error::<T>::Overflow)?;
 
         <Metadatas<T>>::try_mutate(app_id, target, |m| -> DispatchResult {
-            Self::staking(&metadata.challenger, Self::challenge_staking_amount())?;
+            let mut evicted: Balance = Zero::zero();
+            if !metadata.pool.staking.is_zero() {
+                evicted = Self::add_staker(
+                    &metadata.pathfinder,
+                    app_id,
+                    target,
+                    metadata.pool.staking,
+                )?;
+            }
+            evicted = evicted.saturating_add(Self::staking(
+                &metadata.challenger,
+                app_id,
+                target,
+                Self::challenge_staking_amount(),
+            )?);
             *m = Metadata {
                 pool: Pool {
-                    staking,
+                    // Evicted stakes were returned and no longer fund the challenge.
+                    staking: staking.saturating_sub(evicted),
                     earnings,
                 },
                 joint_benefits: challenge.joint_benefits,
@@ -528,11 +708,13 @@ impl<T: Config> ChallengeBase<T::AccountId, AppId, Balance, T::BlockNumber> for
                         Self::is_challenge_timeout(&challenge.last_update),
                         Error::<T>::NoPermission
                     );
-                    Self::staking(who, Self::challenge_staking_amount())?;
+                    let evicted =
+                        Self::staking(who, app_id, target, Self::challenge_staking_amount())?;
+                    challenge.pool.staking = challenge.pool.staking.saturating_sub(evicted);
                     challenge.challenger = who.clone();
                 }
                 let (joint_benefits, restart, score) = up(challenge.score, challenge.remark)?;
-                Self::do_settle(challenge, &restart, &joint_benefits, &score)?;
+                Self::do_settle(app_id, target, challenge, &restart, &joint_benefits, &score)?;
                 Self::after_upload(app_id);
                 Ok(())
             },
@@ -552,7 +734,7 @@ impl<T: Config> ChallengeBase<T::AccountId, AppId, Balance, T::BlockNumber> for
             target,
             |challenge: &mut Metadata<T::AccountId, T::BlockNumber>| -> DispatchResult {
                 ensure!(challenge.is_all_done(), Error::<T>::ProgressErr);
-                Self::do_settle(challenge, &restart, &joint_benefits, &score)
+                Self::do_settle(app_id, target, challenge, &restart, &joint_benefits, &score)
             },
         )
     }
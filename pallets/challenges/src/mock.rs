@@ -24,8 +24,9 @@ use orml_currencies::BasicCurrencyAdapter;
 use orml_traits::parameter_type_with_key;
 
 pub use frame_system as system;
+use frame_system::EnsureRoot;
 use sp_core::H256;
-pub use sp_runtime::{Perbill, Permill};
+pub use sp_runtime::{ModuleId, Perbill, Permill};
 
 pub type Amount = i128;
 pub type AccountId = u64;
@@ -68,7 +69,15 @@ construct_runtime!(
 );
 
 parameter_types! {
-    pub const SocialPoolAccountId: AccountId = 10000;
+    pub const ZdTokenModuleId: ModuleId = ModuleId(*b"zd/socpl");
+    pub const StakingMode: zd_tokens::StakingMode = zd_tokens::StakingMode::Transfer;
+    pub const SolvencyCheckPeriod: u64 = 0;
+    pub const VestingPeriod: u64 = 0;
+    pub const DemurrageRate: Perbill = Perbill::from_percent(0);
+    pub const DemurragePeriod: u64 = 0;
+    pub const MaxBatch: u32 = 100;
+    pub SocialCurrencies: Vec<CurrencyId> = vec![ORS];
+    pub const MaxTrustCount: u32 = 600;
 }
 
 impl zd_tokens::Config for Test {
@@ -76,9 +85,19 @@ impl zd_tokens::Config for Test {
     type CurrencyId = CurrencyId;
     type WeightInfo = ();
     type Currency = Currencies;
-    type SocialPool = SocialPoolAccountId;
+    type ModuleId = ZdTokenModuleId;
+    type UpdateOrigin = EnsureRoot<AccountId>;
+    type StakingMode = StakingMode;
+    type SolvencyCheckPeriod = SolvencyCheckPeriod;
+    type VestingPeriod = VestingPeriod;
+    type DemurrageRate = DemurrageRate;
+    type DemurragePeriod = DemurragePeriod;
+    type TrustBase = ();
+    type MaxTrustCount = MaxTrustCount;
     type Amount = Amount;
     type BaceToken = BaceToken;
+    type SocialCurrencies = SocialCurrencies;
+    type MaxBatch = MaxBatch;
 }
 
 parameter_types! {
@@ -91,8 +110,19 @@ parameter_types! {
     pub const ChallengePerior: BlockNumber = 100;
 }
 
+parameter_types! {
+    pub const WatchDeposit: Balance = 100;
+    pub const MaxWatches: u32 = 2;
+}
+
 impl zd_reputation::Config for Test {
     type Event = Event;
+    type UpdateOrigin = EnsureRoot<AccountId>;
+    type OnRoundAborted = ();
+    type OnThresholdCrossed = ();
+    type Currency = Balances;
+    type WatchDeposit = WatchDeposit;
+    type MaxWatches = MaxWatches;
 }
 
 parameter_types! {
@@ -139,6 +139,13 @@ macro_rules! new_challenge_should_work {
                     );
                     assert_eq!(ZdToken::total_staking(), staking_amount);
                     assert_eq!(ZdToken::free_balance(&CHALLENGER), 1000_000_000_000_000u128 - staking_amount);
+                    assert_eq!(ZdToken::staked_for(&CHALLENGER, &(APP_ID, TARGET)), staking_amount);
+                    // The pathfinder's part of the pool is staked by the caller of `launch`.
+                    let mut stakers = vec![(CHALLENGER, staking_amount)];
+                    if $value.staking > 0 {
+                        stakers.insert(0, (PATHINFER, $value.staking));
+                    }
+                    assert_eq!(ZdChallenges::stakers_of(&APP_ID, &TARGET), stakers);
                 });
             }
         )*
@@ -609,6 +616,7 @@ fn arbitral_should_work() {
             ZdToken::free_balance(&FERDIE),
             1000_000_000_000_000u128 - staking_amount
         );
+        assert_eq!(ZdToken::staked_for(&FERDIE, &(APP_ID, TARGET)), staking_amount);
     });
 }
 
@@ -690,7 +698,7 @@ macro_rules! settle_should_work {
                     };
                     <Metadatas<Test>>::insert(&APP_ID,&TARGET,&init_metadata);
                     // init staking pool
-                    assert_ok!(ZdChallenges::staking(&FERDIE, 10000000));
+                    assert_ok!(ZdToken::staking(&FERDIE, &10000000));
 
                     let free_balance = ZdToken::free_balance(&CHALLENGER);
 
@@ -739,6 +747,68 @@ settle_should_work! {
     settle_should_work_6: (10,100,true,false),
 }
 
+#[test]
+fn restart_should_keep_other_stakes() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(ZdToken::staking(&FERDIE, &10000000));
+        <Metadatas<Test>>::insert(
+            &APP_ID,
+            &TARGET,
+            &Metadata {
+                pool: Pool {
+                    staking: 500,
+                    earnings: 0,
+                },
+                ..DEFAULT_METADATA
+            },
+        );
+        assert_ok!(ZdChallenges::staking(&PATHINFER, &APP_ID, &TARGET, 400));
+        assert_ok!(ZdChallenges::staking(&CHALLENGER, &APP_ID, &TARGET, 100));
+        let challenger_balance = ZdToken::free_balance(&CHALLENGER);
+
+        assert_ok!(ZdChallenges::settle(&APP_ID, &TARGET, true, true, 100));
+
+        // The challenger's stake covers part of the arbitral fee.
+        assert_eq!(ZdToken::free_balance(&CHALLENGER), challenger_balance + 250);
+        assert_eq!(ZdToken::staked_for(&CHALLENGER, &(APP_ID, TARGET)), 0);
+        assert_eq!(ZdToken::staked_for(&PATHINFER, &(APP_ID, TARGET)), 400);
+        assert_eq!(ZdChallenges::stakers_of(&APP_ID, &TARGET), vec![(PATHINFER, 400)]);
+    });
+}
+
+#[test]
+fn stakers_should_be_bounded() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(ZdChallenges::staking(&FERDIE, &APP_ID, &TARGET, 1));
+        for _ in 1..MAX_STAKERS {
+            assert_ok!(ZdChallenges::staking(&CHALLENGER, &APP_ID, &TARGET, 2));
+        }
+        assert_eq!(ZdChallenges::stakers_of(&APP_ID, &TARGET).len(), MAX_STAKERS);
+        let challenger_balance = ZdToken::free_balance(&CHALLENGER);
+        assert_noop!(
+            ZdChallenges::staking(&CHALLENGER, &APP_ID, &TARGET, 1),
+            Error::<Test>::TooManyStakers
+        );
+        assert_eq!(ZdToken::free_balance(&CHALLENGER), challenger_balance);
+
+        // A larger stake takes the place of the smallest, which is returned.
+        let ferdie_balance = ZdToken::free_balance(&FERDIE);
+        assert_eq!(ZdChallenges::staking(&BOB, &APP_ID, &TARGET, 2), Ok(1));
+        let stakers = ZdChallenges::stakers_of(&APP_ID, &TARGET);
+        assert_eq!(stakers.len(), MAX_STAKERS);
+        assert!(stakers.iter().all(|(staker, _)| *staker != FERDIE));
+        assert!(stakers.contains(&(BOB, 2)));
+        assert_eq!(ZdToken::free_balance(&FERDIE), ferdie_balance + 1);
+        assert_eq!(ZdToken::staked_for(&FERDIE, &(APP_ID, TARGET)), 0);
+
+        // Stakes no larger than the smallest are refused.
+        assert_noop!(
+            ZdChallenges::staking(&SWEEPER, &APP_ID, &TARGET, 2),
+            Error::<Test>::TooManyStakers
+        );
+    });
+}
+
 macro_rules! harvest_should_work {
     ($($name:ident: $value:expr,)*) => {
         $(
@@ -747,7 +817,7 @@ macro_rules! harvest_should_work {
                 new_test_ext().execute_with(|| {
                     let (who, status, done, joint_benefits,staking,now) = $value;
                     // init staking pool
-                    assert_ok!(ZdChallenges::staking(&FERDIE, 10000000));
+                    assert_ok!(ZdToken::staking(&FERDIE, &10000000));
                     let init_metadata = Metadata {
                         progress: Progress {
                             total: 100,
@@ -842,3 +912,49 @@ harvest_should_work! {
     harvest_should_work_12: (PATHINFER,ChallengeStatus::Arbitral,10,false,46454,ChallengeTimeout::get() + 2),
     harvest_should_work_13: (SWEEPER,ChallengeStatus::Free,10,false,0,SWEEPER_PERIOD + 2),
 }
+
+#[test]
+fn settle_all_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(ZdToken::staking(&FERDIE, &10000000));
+        <Metadatas<Test>>::insert(
+            &APP_ID,
+            &TARGET,
+            &Metadata {
+                pool: Pool {
+                    staking: 300,
+                    earnings: 200,
+                },
+                status: ChallengeStatus::Examine,
+                ..DEFAULT_METADATA
+            },
+        );
+        <Metadatas<Test>>::insert(
+            &APP_ID,
+            &DAVE,
+            &Metadata {
+                pool: Pool {
+                    staking: 1000,
+                    earnings: 0,
+                },
+                status: ChallengeStatus::Arbitral,
+                joint_benefits: true,
+                ..DEFAULT_METADATA
+            },
+        );
+        assert_ok!(ZdChallenges::staking(&CHALLENGER, &APP_ID, &TARGET, 300));
+        assert_eq!(ZdToken::staked_for(&CHALLENGER, &(APP_ID, TARGET)), 300);
+        let pathfinder_balance = ZdToken::free_balance(&PATHINFER);
+        let challenger_balance = ZdToken::free_balance(&CHALLENGER);
+
+        // No timeout is needed to settle.
+        assert_ok!(ZdChallenges::settle_all(&APP_ID));
+
+        assert_eq!(ZdToken::staked_for(&CHALLENGER, &(APP_ID, TARGET)), 0);
+        assert!(ZdChallenges::stakers_of(&APP_ID, &TARGET).is_empty());
+
+        assert_eq!(ZdToken::free_balance(&PATHINFER), pathfinder_balance + 500);
+        assert_eq!(ZdToken::free_balance(&CHALLENGER), challenger_balance + 500 + 500);
+        assert!(ZdChallenges::is_all_harvest(&APP_ID));
+    });
+}
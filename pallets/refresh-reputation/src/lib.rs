@@ -48,7 +48,9 @@ use frame_support::{
     codec::{Decode, Encode},
     ensure, pallet,
     traits::Get,
-    transactional, RuntimeDebug,
+    transactional,
+    weights::Weight,
+    RuntimeDebug,
 };
 use frame_system::{self as system, ensure_signed};
 use sp_runtime::{traits::Zero, DispatchError, DispatchResult};
@@ -56,7 +58,7 @@ use sp_std::vec::Vec;
 use zd_primitives::{
     fee::SweeperFee, AppId, Balance, ChallengeStatus, Metadata, Pool, Progress, TIRStep,
 };
-use zd_support::{ChallengeBase, MultiBaseToken, Reputation, SeedsBase, TrustBase};
+use zd_support::{ChallengeBase, MultiBaseToken, OnRoundAborted, Reputation, SeedsBase, TrustBase};
 
 #[cfg(test)]
 mod mock;
@@ -101,9 +103,12 @@ pub struct Payroll<Balance, BlockNumber> {
 /// Returns the total amount due to `pathfinder`, including the amount of the staking and the earnings.
 impl<BlockNumber> Payroll<Balance, BlockNumber> {
     fn total_amount<T: Config>(&self) -> Balance {
-        T::UpdateStakingAmount::get()
-            .saturating_mul(self.count.into())
-            .saturating_add(self.total_fee)
+        self.staking_amount::<T>().saturating_add(self.total_fee)
+    }
+
+    /// Returns the amount staked by `pathfinder` for the refreshed users.
+    fn staking_amount<T: Config>(&self) -> Balance {
+        T::UpdateStakingAmount::get().saturating_mul(self.count.into())
     }
 }
 
@@ -161,6 +166,10 @@ pub mod pallet {
         #[pallet::constant]
         type RefRepuTiomeOut: Get<Self::BlockNumber>;
 
+        /// The maximum number of users anyone can trust.
+        #[pallet::constant]
+        type MaxTrustCount: Get<u32>;
+
         /// The weight information of this pallet.
         type WeightInfo: WeightInfo;
     }
@@ -305,6 +314,7 @@ pub mod pallet {
                 .try_fold::<_, _, Result<Balance, DispatchError>>(
                     0u128,
                     |acc: Balance, (pathfinder, payroll)| {
+                        Self::forfeit(&pathfinder, &payroll)?;
                         let (sweeper_fee, without_fee) = payroll.total_amount::<T>().with_fee();
 
                         T::MultiBaseToken::release(&pathfinder, &without_fee)?;
@@ -320,7 +330,7 @@ pub mod pallet {
         }
 
         /// Refresh the reputation value of a group of users.
-        #[pallet::weight(T::WeightInfo::refresh((user_scores.len() as u32).max(1u32)))]
+        #[pallet::weight(Pallet::<T>::refresh_weight((user_scores.len() as u32).max(1u32)))]
         #[transactional]
         pub fn refresh(
             origin: OriginFor<T>,
@@ -345,7 +355,7 @@ pub mod pallet {
             let amount = T::UpdateStakingAmount::get()
                 .checked_mul(user_count as Balance)
                 .ok_or(Error::<T>::Overflow)?;
-            T::MultiBaseToken::staking(&pathfinder, &amount)?;
+            T::MultiBaseToken::stake(&pathfinder, &(APP_ID, pathfinder.clone()), &amount)?;
             let total_fee = user_scores
                 .iter()
                 .try_fold::<_, _, Result<Balance, DispatchError>>(
@@ -387,8 +397,9 @@ pub mod pallet {
             let now_block_number = Self::now();
             let payroll = Payrolls::<T>::take(&pathfinder);
             Self::can_harvest(&payroll, &now_block_number)?;
+            Self::unstake(&pathfinder, &payroll)?;
+            T::MultiBaseToken::release(&pathfinder, &payroll.total_fee)?;
             let total_amount = payroll.total_amount::<T>();
-            T::MultiBaseToken::release(&pathfinder, &total_amount)?;
             <Records<T>>::remove_prefix(&pathfinder);
             Self::deposit_event(Event::RefreshedHarvested(pathfinder, total_amount));
             Ok(().into())
@@ -412,6 +423,7 @@ pub mod pallet {
             let payroll = Payrolls::<T>::take(&pathfinder);
             let now_block_number = Self::now();
             Self::can_harvest(&payroll, &now_block_number)?;
+            Self::forfeit(&pathfinder, &payroll)?;
             let (sweeper_fee, without_fee) = payroll
                 .total_amount::<T>()
                 .checked_with_fee(payroll.update_at, Self::now())
@@ -486,13 +498,22 @@ pub mod pallet {
                 f.total_fee = f.total_fee.saturating_sub(record.fee);
                 f.count = f.count.saturating_sub(1);
             });
+            // The stake for `target` goes to the challenge, which returns it if the
+            // pathfinder wins and forfeits it if the pathfinder loses.
+            let staking = T::UpdateStakingAmount::get();
+            T::MultiBaseToken::restake(
+                &pathfinder,
+                &(APP_ID, pathfinder.clone()),
+                &(APP_ID, target.clone()),
+                &staking,
+            )?;
 
             T::ChallengeBase::launch(
                 &APP_ID,
                 &target,
                 &Metadata {
                     pool: Pool {
-                        staking: Zero::zero(),
+                        staking,
                         earnings: record.fee,
                     },
                     remark: reputation,
@@ -594,6 +615,19 @@ pub mod pallet {
 impl<T: Config> Pallet<T> {
     // pub
 
+    /// The benchmarked `refresh` of `n` users, plus the demurrage applied to each of the
+    /// up to `MaxTrustCount` users they share with, and to the staking and bonus totals,
+    /// in each of the social currencies.
+    pub fn refresh_weight(n: u32) -> Weight {
+        let shares = (n as Weight)
+            .saturating_mul(T::MultiBaseToken::social_currency_count().max(1) as Weight);
+        let trustees = shares.saturating_mul(T::MaxTrustCount::get() as Weight);
+        let per_trustee = 10_000 + T::DbWeight::get().reads_writes(1, 1);
+        T::WeightInfo::refresh(n)
+            .saturating_add(T::DbWeight::get().reads_writes(2, 2).saturating_mul(shares))
+            .saturating_add(per_trustee.saturating_mul(trustees))
+    }
+
     /// Add `pathfinder` `amount` of accounts payable, and `count` updates to the original,
     /// and set last active time to `now`.
     pub fn mutate_payroll(
@@ -670,9 +704,14 @@ impl<T: Config> Pallet<T> {
         Ok(fee)
     }
 
+    /// Share the social currencies of `user` with those it trusts, returning the fee.
     pub(crate) fn share(user: &T::AccountId) -> Balance {
         let targets = T::TrustBase::get_trust_old(user);
-        T::MultiBaseToken::share(user, &targets[..])
+        T::MultiBaseToken::social_currencies(user)
+            .into_iter()
+            .fold(Zero::zero(), |fee: Balance, currency_id| {
+                fee.saturating_add(T::MultiBaseToken::share(currency_id, user, &targets[..]))
+            })
     }
 
     pub(crate) fn get_dist(
@@ -782,6 +821,30 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
+    /// Return the stake of `pathfinder` for `payroll`.
+    fn unstake(
+        pathfinder: &T::AccountId,
+        payroll: &Payroll<Balance, T::BlockNumber>,
+    ) -> DispatchResult {
+        T::MultiBaseToken::unstake(
+            pathfinder,
+            &(APP_ID, pathfinder.clone()),
+            &payroll.staking_amount::<T>(),
+        )
+    }
+
+    /// Hand the stake of `pathfinder` for `payroll` over to the pool, to pay the sweeper.
+    fn forfeit(
+        pathfinder: &T::AccountId,
+        payroll: &Payroll<Balance, T::BlockNumber>,
+    ) -> DispatchResult {
+        T::MultiBaseToken::forfeit(
+            pathfinder,
+            &(APP_ID, pathfinder.clone()),
+            &payroll.staking_amount::<T>(),
+        )
+    }
+
     fn check_timeout(now: &T::BlockNumber) -> DispatchResult {
         ensure!(
             *now < <StartedAt<T>>::get() + T::RefRepuTiomeOut::get(),
@@ -790,3 +853,18 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 }
+
+impl<T: Config> OnRoundAborted for Pallet<T> {
+    fn on_round_aborted() -> DispatchResult {
+        T::ChallengeBase::settle_all(&APP_ID)?;
+        for (pathfinder, payroll) in Payrolls::<T>::drain() {
+            Self::unstake(&pathfinder, &payroll)?;
+            T::MultiBaseToken::release(&pathfinder, &payroll.total_fee)?;
+        }
+        <Records<T>>::remove_all();
+        <Paths<T>>::remove_all();
+        <StartedAt<T>>::kill();
+        T::TrustBase::remove_all_tmp();
+        Ok(())
+    }
+}
@@ -16,12 +16,17 @@ use crate as zd_refresh_reputation;
 use sp_runtime::{
     traits::{BlakeTwo256, IdentityLookup}, testing::Header,
 };
-use frame_support::{construct_runtime, parameter_types, traits::GenesisBuild};
+use frame_support::{
+    construct_runtime, parameter_types,
+    traits::{GenesisBuild, Get},
+};
 use frame_system as system;
+use frame_system::EnsureRoot;
 use orml_currencies::BasicCurrencyAdapter;
 use orml_traits::parameter_type_with_key;
 use sp_core::H256;
-use sp_runtime::Perbill;
+use sp_runtime::{ModuleId, Perbill};
+use std::cell::RefCell;
 use zd_primitives::Balance;
 
 pub type Amount = i128;
@@ -105,9 +110,21 @@ parameter_types! {
     pub const MaxTrustCount: u32 = 600;
 }
 
+parameter_types! {
+    pub const MaxSeeds: u32 = 500;
+    pub const MaxSeedTerms: u32 = 0;
+    pub const SeedCooldown: u32 = 0;
+    pub const MaxMissedHeartbeats: u32 = 0;
+}
+
 impl zd_seeds::Config for Test {
     type Event = Event;
     type Reputation = ZdReputation;
+    type UpdateOrigin = EnsureRoot<AccountId>;
+    type MaxSeeds = MaxSeeds;
+    type MaxSeedTerms = MaxSeedTerms;
+    type SeedCooldown = SeedCooldown;
+    type MaxMissedHeartbeats = MaxMissedHeartbeats;
 }
 
 impl zd_trust::Config for Test {
@@ -119,8 +136,19 @@ impl zd_trust::Config for Test {
 	type WeightInfo = ();
 }
 
+parameter_types! {
+    pub const WatchDeposit: Balance = 100;
+    pub const MaxWatches: u32 = 2;
+}
+
 impl zd_reputation::Config for Test {
     type Event = Event;
+    type UpdateOrigin = EnsureRoot<AccountId>;
+    type OnRoundAborted = ZdRefreshReputation;
+    type OnThresholdCrossed = ();
+    type Currency = Balances;
+    type WatchDeposit = WatchDeposit;
+    type MaxWatches = MaxWatches;
 }
 
 impl zd_refresh_reputation::Config for Test {
@@ -134,6 +162,7 @@ impl zd_refresh_reputation::Config for Test {
     type ChallengeBase = Challenges;
     type SeedsBase = ZdSeeds;
     type RefRepuTiomeOut = RefRepuTiomeOut;
+    type MaxTrustCount = MaxTrustCount;
     type WeightInfo = ();
 }
 
@@ -168,7 +197,33 @@ impl orml_tokens::Config for Test {
 }
 
 parameter_types! {
-    pub const SocialPoolAccountId: AccountId = 10000;
+    pub const ZdTokenModuleId: ModuleId = ModuleId(*b"zd/socpl");
+    pub const SolvencyCheckPeriod: u64 = 0;
+    pub const VestingPeriod: u64 = 0;
+    pub const DemurrageRate: Perbill = Perbill::from_percent(0);
+    pub const DemurragePeriod: u64 = 0;
+    pub const MaxBatch: u32 = 100;
+    pub SocialCurrencies: Vec<CurrencyId> = vec![ORS];
+}
+
+thread_local! {
+    static STAKING_MODE: RefCell<zd_tokens::StakingMode> =
+        RefCell::new(zd_tokens::StakingMode::Transfer);
+}
+
+/// Staking mode that can be switched by the tests.
+pub struct MockStakingMode;
+
+impl MockStakingMode {
+    pub fn set(mode: zd_tokens::StakingMode) {
+        STAKING_MODE.with(|m| *m.borrow_mut() = mode);
+    }
+}
+
+impl Get<zd_tokens::StakingMode> for MockStakingMode {
+    fn get() -> zd_tokens::StakingMode {
+        STAKING_MODE.with(|m| *m.borrow())
+    }
 }
 
 impl zd_tokens::Config for Test {
@@ -176,9 +231,19 @@ impl zd_tokens::Config for Test {
     type CurrencyId = CurrencyId;
     type WeightInfo = ();
     type Currency = Currencies;
-    type SocialPool = SocialPoolAccountId;
+    type ModuleId = ZdTokenModuleId;
+    type UpdateOrigin = EnsureRoot<AccountId>;
+    type StakingMode = MockStakingMode;
+    type SolvencyCheckPeriod = SolvencyCheckPeriod;
+    type VestingPeriod = VestingPeriod;
+    type DemurrageRate = DemurrageRate;
+    type DemurragePeriod = DemurragePeriod;
+    type TrustBase = ZdTrust;
+    type MaxTrustCount = MaxTrustCount;
     type Amount = Amount;
     type BaceToken = BaceToken;
+    type SocialCurrencies = SocialCurrencies;
+    type MaxBatch = MaxBatch;
 }
 
 impl system::Config for Test {
@@ -15,7 +15,8 @@
 use super::*;
 use crate::mock::{Event, *};
 use frame_support::{assert_err_ignore_postinfo, assert_noop, assert_ok};
-use zd_primitives::per_social_currency;
+use orml_traits::MultiReservableCurrency;
+use zd_primitives::ReputationValue;
 
 fn new_test_ext() -> sp_io::TestExternalities {
     let mut ext = ExtBuilder::default().build();
@@ -127,7 +128,13 @@ fn start_with_payrolls() {
         assert_ok!(ZdToken::staking(&FERDIE, &1_000_000_000_000u128));
 
         for (i, payroll) in INIT_PAYROLLS.iter().enumerate() {
-            <Payrolls<Test>>::insert(&INIT_ACCOUNT[i].account, payroll);
+            let account = INIT_ACCOUNT[i].account;
+            <Payrolls<Test>>::insert(&account, payroll);
+            zd_tokens::StakingLedger::<Test>::insert(
+                &account,
+                &(APP_ID, account),
+                UpdateStakingAmount::get() * (payroll.count as Balance),
+            );
         }
 
         let who_balance = ZdToken::free_balance(&SWEEPRT);
@@ -152,6 +159,9 @@ fn start_with_payrolls() {
         });
 
         assert_eq!(ZdToken::free_balance(&SWEEPRT), who_balance + total_fee);
+        for a in INIT_ACCOUNT.iter() {
+            assert!(ZdToken::stakes_of(&a.account).is_empty());
+        }
     });
 }
 
@@ -166,6 +176,7 @@ fn refresh_should_work() {
         for a in INIT_ACCOUNT.iter() {
             assert_ok!(ZdToken::transfer_social(
                 Origin::signed(SWEEPRT),
+                ORS,
                 a.account,
                 a.soc_amount
             ));
@@ -199,13 +210,13 @@ fn refresh_should_work() {
         for a in INIT_ACCOUNT[..4].iter() {
             assert_eq!(
                 <Records<Test>>::get(&PATHFINDER, a.account).fee,
-                per_social_currency::PRE_FEE.mul_floor(a.soc_amount)
+                ZdToken::social_ratios().fee.mul_floor(a.soc_amount)
             );
         }
 
         let total_fee = INIT_ACCOUNT[..4]
             .iter()
-            .map(|f| per_social_currency::PRE_FEE.mul_floor(f.soc_amount))
+            .map(|f| ZdToken::social_ratios().fee.mul_floor(f.soc_amount))
             .sum();
 
         assert_eq!(
@@ -213,6 +224,10 @@ fn refresh_should_work() {
             user_scores.len() as u32
         );
         assert_eq!(<Payrolls<Test>>::get(&PATHFINDER).total_fee, total_fee);
+        assert_eq!(
+            ZdToken::staked_for(&PATHFINDER, &(APP_ID, PATHFINDER)),
+            UpdateStakingAmount::get() * (user_scores.len() as Balance)
+        );
 
         let new_event = Event::zd_refresh_reputation(crate::Event::ReputationRefreshed(PATHFINDER,user_scores.len() as u32,total_fee));
         assert!(System::events().iter().any(|record| record.event == new_event));
@@ -273,6 +288,11 @@ macro_rules! harvest_ref_all_should_work {
                         update_at: 1,
                     });
                     let total_amount = UpdateStakingAmount::get() * $value.0 + $value.1;
+                    zd_tokens::StakingLedger::<Test>::insert(
+                        &PATHFINDER,
+                        &(APP_ID, PATHFINDER),
+                        UpdateStakingAmount::get() * $value.0,
+                    );
                     assert_ok!(ZdToken::staking(&ALICE, &1_000_000_000_000u128));
                     for a in INIT_ACCOUNT.iter() {
                         <Records<Test>>::insert(&PATHFINDER,&a.account,Record {
@@ -285,6 +305,7 @@ macro_rules! harvest_ref_all_should_work {
                     assert_ok!(ZdRefreshReputation::harvest_ref_all(Origin::signed(PATHFINDER)));
                     let new_balances = ZdToken::free_balance(&PATHFINDER);
                     assert_eq!(new_balances - old_balances, total_amount);
+                    assert_eq!(ZdToken::staked_for(&PATHFINDER, &(APP_ID, PATHFINDER)), 0);
                     for a in INIT_ACCOUNT.iter() {
                         assert!(<Records<Test>>::try_get(&PATHFINDER,&a.account).is_err());
                     }
@@ -307,7 +328,7 @@ harvest_ref_all_should_work! {
 fn init_sys(score: u32) {
     let init_seeds = vec![SEED1, SEED2, SEED3, SEED4];
     for seed in init_seeds {
-        ZdSeeds::add_seed(&seed);
+        assert_ok!(ZdSeeds::add_seed(&seed));
     }
     let init_paths = vec![
         vec![SEED1, ALICE, TARGET],
@@ -328,6 +349,7 @@ fn init_sys(score: u32) {
     }
     assert_ok!(ZdToken::transfer_social(
         Origin::signed(ALICE),
+        ORS,
         TARGET,
         1000
     ));
@@ -357,11 +379,50 @@ fn challenge_should_work() {
         assert_eq!(payroll.total_fee, 0);
         assert_eq!(payroll.count, 0);
         assert_eq!(payroll.update_at, 1);
+        assert_eq!(ZdToken::staked_for(&PATHFINDER, &(APP_ID, PATHFINDER)), 0);
+        assert_eq!(
+            ZdToken::staked_for(&PATHFINDER, &(APP_ID, TARGET)),
+            UpdateStakingAmount::get()
+        );
         let new_event = Event::zd_refresh_reputation(crate::Event::Challenge(CHALLENGER, TARGET));
         assert!(System::events().iter().any(|record| record.event == new_event));
     });
 }
 
+#[test]
+fn challenge_should_keep_reserved_stake() {
+    new_test_ext().execute_with(|| {
+        MockStakingMode::set(zd_tokens::StakingMode::Reserve);
+        init_sys(100);
+        let staking = UpdateStakingAmount::get();
+        let free_balance = ZdToken::free_balance(&PATHFINDER);
+        assert_eq!(Currencies::reserved_balance(ORS, &PATHFINDER), staking);
+
+        assert_ok!(ZdRefreshReputation::challenge(
+            Origin::signed(CHALLENGER),
+            TARGET,
+            PATHFINDER,
+            3,
+            20
+        ));
+        // The stake stays reserved, now for the challenge.
+        assert_eq!(ZdToken::free_balance(&PATHFINDER), free_balance);
+        assert_eq!(Currencies::reserved_balance(ORS, &PATHFINDER), staking);
+        assert_eq!(ZdToken::staked_for(&PATHFINDER, &(APP_ID, PATHFINDER)), 0);
+        assert_eq!(ZdToken::staked_for(&PATHFINDER, &(APP_ID, TARGET)), staking);
+
+        // Unanswered, the challenge is won by the pathfinder, who gets the stake back.
+        System::set_block_number(ChallengeTimeout::get() + 10);
+        assert_ok!(ZdRefreshReputation::harvest_challenge(
+            Origin::signed(PATHFINDER),
+            TARGET
+        ));
+        assert_eq!(Currencies::reserved_balance(ORS, &PATHFINDER), 0);
+        assert_eq!(ZdToken::staked_for(&PATHFINDER, &(APP_ID, TARGET)), 0);
+        assert!(ZdToken::free_balance(&PATHFINDER) > free_balance + staking);
+    });
+}
+
 #[test]
 fn challenge_should_fail() {
     new_test_ext().execute_with(|| {
@@ -570,8 +631,14 @@ fn arbitral_should_work() {
             paths.clone()
         ));
 
-        assert_eq!(ZdReputation::get_reputation(&TARGET), Some(0));
-        assert_eq!(ZdReputation::get_reputation(&ALICE), Some(0));
+        assert_eq!(
+            ZdReputation::get_reputation(&TARGET),
+            Some(ReputationValue::Computed(0))
+        );
+        assert_eq!(
+            ZdReputation::get_reputation(&ALICE),
+            Some(ReputationValue::Computed(0))
+        );
 
         assert_ok!(ZdRefreshReputation::arbitral(
             Origin::signed(CHALLENGER),
@@ -734,3 +801,23 @@ fn arbitral_should_fail() {
         );
     });
 }
+
+#[test]
+fn abort_round_should_work() {
+    new_test_ext().execute_with(|| {
+        let old_balances = ZdToken::free_balance(&PATHFINDER);
+        init_sys(21);
+        let fee = <Records<Test>>::get(&PATHFINDER, &TARGET).fee;
+        assert_eq!(ZdReputation::get_reputation_new(&TARGET), Some(21));
+
+        assert_ok!(ZdReputation::abort_round(Origin::root()));
+
+        assert_eq!(ZdToken::free_balance(&PATHFINDER) - old_balances, fee);
+        assert!(<Payrolls<Test>>::try_get(&PATHFINDER).is_err());
+        assert_eq!(ZdToken::staked_for(&PATHFINDER, &(APP_ID, PATHFINDER)), 0);
+        assert!(<Records<Test>>::try_get(&PATHFINDER, &TARGET).is_err());
+        assert!(!<StartedAt<Test>>::exists());
+        assert_eq!(ZdReputation::get_reputation_new(&TARGET), None);
+        assert!(ZdReputation::is_step(&TIRStep::Free));
+    });
+}
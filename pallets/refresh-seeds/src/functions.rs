@@ -97,7 +97,46 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
+    /// Upper bound of the weight `hand_first_time` adds to the first harvest,
+    /// reading each candidate and its seed term and removing the seeds.
+    pub(crate) fn hand_first_time_weight() -> Weight {
+        let candidates = T::MaxCandidates::get() as Weight;
+        let seeds = T::SeedsBase::max_seed_count() as Weight;
+        T::DbWeight::get().reads_writes(
+            candidates.saturating_mul(2).saturating_add(1),
+            seeds.saturating_add(1),
+        )
+    }
+
+    /// Upper bound of the weight of dropping the seeds that reached the seed
+    /// term limit.
+    pub(crate) fn drop_ineligible_weight() -> Weight {
+        let seeds = T::SeedsBase::max_seed_count() as Weight;
+        T::DbWeight::get().reads_writes(
+            seeds.saturating_mul(2).saturating_add(1),
+            seeds.saturating_mul(2).saturating_add(1),
+        )
+    }
+
+    /// Upper bound of the weight of keeping the seeds for an abort and dropping
+    /// those that missed too many heartbeats when a round starts.
+    pub(crate) fn drop_inactive_weight() -> Weight {
+        let seeds = T::SeedsBase::max_seed_count() as Weight;
+        T::DbWeight::get().reads_writes(
+            seeds.saturating_mul(4).saturating_add(3),
+            seeds.saturating_mul(3).saturating_add(2),
+        )
+    }
+
     pub(crate) fn hand_first_time(score_list: &mut Vec<u64>) {
+        // Candidates that reached their seed term limit take no seat.
+        let ineligible = <Candidates<T>>::iter()
+            .filter(|(target, _)| !T::SeedsBase::is_eligible(target));
+        for (_, candidate) in ineligible {
+            if let Ok(index) = score_list.binary_search(&candidate.score) {
+                score_list.remove(index);
+            }
+        }
         let max_seed_count = T::MaxSeedCount::get() as usize;
         let len = score_list.len();
         if len > max_seed_count {
@@ -177,14 +216,37 @@ impl<T: Config> Pallet<T> {
         Ok(nodes.to_vec())
     }
 
-    pub(crate) fn restart(target: &T::AccountId, pathfinder: &T::AccountId, score: &u64) {
-        <Candidates<T>>::mutate(&target, |c| {
+    /// Returns the stake held by a candidate, less the part at stake in its challenge.
+    pub(crate) fn candidate_staking(has_challenge: bool) -> Balance {
+        match has_challenge {
+            true => T::SeedReservStaking::get(),
+            false => T::SeedStakingAmount::get(),
+        }
+    }
+
+    pub(crate) fn restart(
+        target: &T::AccountId,
+        pathfinder: &T::AccountId,
+        score: &u64,
+    ) -> DispatchResult {
+        <Candidates<T>>::try_mutate(&target, |c| -> DispatchResult {
+            // The new pathfinder takes over the stake of the candidate.
+            if c.pathfinder != *pathfinder {
+                T::MultiBaseToken::move_stake(
+                    &c.pathfinder,
+                    pathfinder,
+                    &(APP_ID, target.clone()),
+                    &Self::candidate_staking(c.has_challenge),
+                )?;
+            }
             Self::mutate_score(&c.score, score);
             c.score = *score;
             c.pathfinder = pathfinder.clone();
-        });
+            Ok(())
+        })?;
         Self::remove_challenge(target);
         Self::deposit_event(Event::ChallengeRestarted(target.clone(), *score));
+        Ok(())
     }
 
     pub(crate) fn remove_challenge(target: &T::AccountId) {
@@ -456,7 +518,7 @@ impl<T: Config> Pallet<T> {
         )?;
 
         match maybe_score {
-            Some(score) => Self::restart(target, challenger, &score),
+            Some(score) => Self::restart(target, challenger, &score)?,
             None => <MissedPaths<T>>::insert(target, nodes.to_vec()),
         }
 
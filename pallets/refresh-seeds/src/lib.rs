@@ -107,6 +107,7 @@
 //! - `invalid_evidence` - The evidence was proved to be wrong.
 //! - `harvest_challenge` - Receive the proceeds of the challenge.
 //! - `harvest_seed` - Receive seed proceeds.
+//! - `skip_seeds` - Keep the current seeds when the seed step timed out without candidates.
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
 
@@ -114,7 +115,9 @@ use frame_support::{
     codec::{Decode, Encode},
     ensure,
     traits::Get,
-    transactional, RuntimeDebug,
+    transactional,
+    weights::Weight,
+    RuntimeDebug,
 };
 use frame_system::{self as system};
 use sp_runtime::{traits::Zero, DispatchError, DispatchResult};
@@ -122,8 +125,8 @@ use sp_std::{cmp::Ordering, vec::Vec};
 
 pub use orml_utilities::OrderedSet;
 
-use zd_primitives::{fee::SweeperFee, AppId, Balance, Metadata, Pool, TIRStep};
-use zd_support::{ChallengeBase, MultiBaseToken, Reputation, SeedsBase, TrustBase};
+use zd_primitives::{fee::SweeperFee, AppId, Balance, Metadata, Pool, SeedInfo, TIRStep};
+use zd_support::{ChallengeBase, MultiBaseToken, OnRoundAborted, Reputation, SeedsBase, TrustBase};
 
 pub use pallet::*;
 
@@ -172,10 +175,19 @@ pub mod pallet {
         #[pallet::constant]
         type MaxSeedCount: Get<u32>;
 
+        /// Maximum number of seed candidates in a round.
+        #[pallet::constant]
+        type MaxCandidates: Get<u32>;
+
         /// Confirmation period.
         #[pallet::constant]
         type ConfirmationPeriod: Get<Self::BlockNumber>;
 
+        /// After this period without any seed candidates, the current seeds can be kept
+        /// and the system moved on to refreshing reputation.
+        #[pallet::constant]
+        type RefSeedTimeOut: Get<Self::BlockNumber>;
+
         /// The weight information of this pallet.
         type WeightInfo: WeightInfo;
     }
@@ -223,6 +235,18 @@ pub mod pallet {
     #[pallet::getter(fn seeds_confirmed)]
     pub type SeedsConfirmed<T: Config> = StorageValue<_, bool, ValueQuery>;
 
+    /// Start time for this round of seed refreshing.
+    #[pallet::storage]
+    #[pallet::getter(fn started_at)]
+    pub type StartedAt<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
+    /// The seeds at the start of the current round, restored if the round is
+    /// aborted.
+    #[pallet::storage]
+    #[pallet::getter(fn previous_seeds)]
+    pub type PreviousSeeds<T: Config> =
+        StorageValue<_, Vec<(T::AccountId, SeedInfo<T::AccountId>)>, OptionQuery>;
+
     #[pallet::event]
     #[pallet::metadata(T::AccountId = "AccountId")]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
@@ -261,6 +285,8 @@ pub mod pallet {
         ChallengeRestarted(T::AccountId, u64),
         /// All seeds have been selected \[number \]
         SeedsSelected(u32),
+        /// The seed step timed out and the current seeds were kept. \[who, number\]
+        SeedsKept(T::AccountId, u32),
     }
 
     #[pallet::error]
@@ -345,6 +371,14 @@ pub mod pallet {
         NoPathExists,
         /// Candidate does not exist or has been harvested
         CandidateNotExist,
+        /// The seed step has not timed out yet
+        NotYetTimedOut,
+        /// There are still candidates to be harvested
+        StillCandidates,
+        /// The target reached the seed term limit and is cooling down
+        TermLimitReached,
+        /// Maximum number of seed candidates reached
+        TooManyCandidates,
     }
 
     #[pallet::hooks]
@@ -353,11 +387,20 @@ pub mod pallet {
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         /// Start seed refreshing.
-        #[pallet::weight(T::WeightInfo::start())]
+        ///
+        /// The social currency ratios set by governance come into force, and seeds
+        /// that missed too many heartbeats are dropped.
+        #[pallet::weight(
+            T::WeightInfo::start().saturating_add(Pallet::<T>::drop_inactive_weight())
+        )]
         #[transactional]
         pub fn start(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
             T::Reputation::new_round()?;
+            T::MultiBaseToken::start_round(T::Reputation::get_finalized_nonce().saturating_add(1));
+            <PreviousSeeds<T>>::put(T::SeedsBase::get_seed_infos());
+            T::SeedsBase::drop_inactive();
+            <StartedAt<T>>::put(Self::now());
             Self::deposit_event(Event::RefershSeedStared(who));
             Ok(().into())
         }
@@ -365,6 +408,11 @@ pub mod pallet {
         /// Add `target` as a seed candidate with a centrality score of `score`.
         ///
         /// Funds from the caller's `SeedStakingAmount` will be deducted.
+        ///
+        /// Is a no-op if:
+        ///
+        /// - `target` reached the seed term limit and is cooling down, or
+        /// - there are already `MaxCandidates` candidates.
         #[pallet::weight(T::WeightInfo::add())]
         #[transactional]
         pub fn add(
@@ -378,7 +426,19 @@ pub mod pallet {
                 !<Candidates<T>>::contains_key(target.clone()),
                 Error::<T>::AlreadyExist
             );
-            T::MultiBaseToken::staking(&pathfinder, &T::SeedStakingAmount::get())?;
+            ensure!(
+                T::SeedsBase::is_eligible(&target),
+                Error::<T>::TermLimitReached
+            );
+            ensure!(
+                (Self::get_score_list().len() as u32) < T::MaxCandidates::get(),
+                Error::<T>::TooManyCandidates
+            );
+            T::MultiBaseToken::stake(
+                &pathfinder,
+                &(APP_ID, target.clone()),
+                &T::SeedStakingAmount::get(),
+            )?;
             Self::candidate_insert(&target, &pathfinder, &score);
             T::Reputation::set_last_refresh_at();
             Self::deposit_event(Event::NewCandidate(pathfinder, target, score));
@@ -763,7 +823,7 @@ pub mod pallet {
                 &target,
                 |_, _| -> Result<bool, DispatchError> { Ok(false) },
             )?;
-            Self::restart(&target, &challenger, &maybe_score.unwrap_or_default());
+            Self::restart(&target, &challenger, &maybe_score.unwrap_or_default())?;
             Self::deposit_event(Event::ShorterPresented(challenger, target, index));
             Ok(().into())
         }
@@ -817,7 +877,7 @@ pub mod pallet {
                     Ok(false)
                 },
             )?;
-            Self::restart(&target, &challenger, &maybe_score.unwrap_or_default());
+            Self::restart(&target, &challenger, &maybe_score.unwrap_or_default())?;
             Self::deposit_event(Event::EvidenceOfNumTooLowPresented(
                 challenger, target, index,
             ));
@@ -861,7 +921,7 @@ pub mod pallet {
                 },
             )?;
             if through_target {
-                Self::restart(&target, &challenger, &score);
+                Self::restart(&target, &challenger, &score)?;
             }
             Self::deposit_event(Event::EvidenceOfInvalidPresented(challenger, target, score));
             Ok(().into())
@@ -892,7 +952,11 @@ pub mod pallet {
         /// e.g. if there are 100 seed candidates but the maximum number of seeds
         /// is 90, the 90 with the highest score will be taken. If the 91st and
         /// 90th scores are the same, the first to be claimed will be confirmed.
-        #[pallet::weight(T::WeightInfo::harvest_seed())]
+        /// Candidates that reached the seed term limit take no seat and receive no
+        /// bonus.
+        #[pallet::weight(
+            T::WeightInfo::harvest_seed().saturating_add(Pallet::<T>::hand_first_time_weight())
+        )]
         #[transactional]
         pub fn harvest_seed(
             origin: OriginFor<T>,
@@ -918,13 +982,12 @@ pub mod pallet {
             );
 
             let candidate = <Candidates<T>>::take(&target);
-            let staking_amount = if candidate.has_challenge {
-                T::SeedReservStaking::get()
-            } else {
-                T::SeedStakingAmount::get()
-            };
+            let staking_amount = Self::candidate_staking(candidate.has_challenge);
             let (bonus, maybe_index) =
-                match !score_list.is_empty() && candidate.score >= score_list[0] {
+                match T::SeedsBase::is_eligible(&target)
+                    && !score_list.is_empty()
+                    && candidate.score >= score_list[0]
+                {
                     true => {
                         if let Ok(index) = score_list.binary_search(&candidate.score) {
                             (
@@ -937,36 +1000,107 @@ pub mod pallet {
                     }
                     false => (Zero::zero(), None),
                 };
-            let total_amount = bonus
-                .checked_add(staking_amount)
-                .ok_or(Error::<T>::Overflow)?;
+            let staking_id = (APP_ID, target.clone());
             match who != candidate.pathfinder {
                 true => {
+                    let total_amount = bonus
+                        .checked_add(staking_amount)
+                        .ok_or(Error::<T>::Overflow)?;
                     let last = T::Reputation::get_last_refresh_at();
                     let (s_amount, p_amount) = total_amount
                         .checked_with_fee(last, Self::now())
                         .ok_or(Error::<T>::SweeprtFail)?;
+                    // The sweeper's fee is paid out of the stake.
+                    T::MultiBaseToken::forfeit(
+                        &candidate.pathfinder,
+                        &staking_id,
+                        &staking_amount,
+                    )?;
                     T::MultiBaseToken::release(&who, &s_amount)?;
                     T::MultiBaseToken::release(&candidate.pathfinder, &p_amount)?;
                 }
                 false => {
-                    T::MultiBaseToken::release(&candidate.pathfinder, &total_amount)?;
+                    T::MultiBaseToken::unstake(
+                        &candidate.pathfinder,
+                        &staking_id,
+                        &staking_amount,
+                    )?;
+                    T::MultiBaseToken::release(&candidate.pathfinder, &bonus)?;
                 }
             }
             T::MultiBaseToken::cut_bonus(&bonus)?;
             if let Some(index) = maybe_index {
-                T::SeedsBase::add_seed(&target);
+                T::SeedsBase::add_computed_seed(&target, candidate.score, &candidate.pathfinder)?;
                 score_list.remove(index);
             }
             if Self::is_all_harvest() {
                 <SeedsConfirmed<T>>::put(false);
+                T::SeedsBase::archive_seeds();
                 T::Reputation::set_step(&TIRStep::Reputation);
             } else if !is_all_confirmed {
                 <SeedsConfirmed<T>>::put(true);
             }
             Self::deposit_event(Event::SeedHarvested(who, target));
             <ScoreList<T>>::put(score_list);
+            match is_all_confirmed {
+                true => Ok(Some(T::WeightInfo::harvest_seed()).into()),
+                false => Ok(().into()),
+            }
+        }
+
+        /// Keep the current seeds and move on to refreshing reputation.
+        ///
+        /// The current seeds are those of the previous round, along with any set by
+        /// governance since. Seeds that reached the seed term limit are dropped.
+        ///
+        /// Is a no-op if:
+        ///
+        /// - `RefSeedTimeOut` has not passed since the round started, or
+        /// - there are still candidates, which are to be harvested instead.
+        #[pallet::weight(
+            T::WeightInfo::skip_seeds().saturating_add(Pallet::<T>::drop_ineligible_weight())
+        )]
+        #[transactional]
+        pub fn skip_seeds(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::check_step()?;
+            ensure!(
+                Self::started_at() + T::RefSeedTimeOut::get() < Self::now(),
+                Error::<T>::NotYetTimedOut
+            );
+            ensure!(Self::is_all_harvest(), Error::<T>::StillCandidates);
+            <ScoreList<T>>::kill();
+            <SeedsConfirmed<T>>::kill();
+            T::SeedsBase::drop_ineligible();
+            T::SeedsBase::archive_seeds();
+            T::Reputation::set_step(&TIRStep::Reputation);
+            Self::deposit_event(Event::SeedsKept(who, T::SeedsBase::get_seeds().len() as u32));
             Ok(().into())
         }
     }
 }
+
+impl<T: Config> OnRoundAborted for Pallet<T> {
+    fn on_round_aborted() -> DispatchResult {
+        T::ChallengeBase::settle_all(&APP_ID)?;
+        for (target, candidate) in <Candidates<T>>::drain() {
+            // The challenged part of the staking has been settled with the challenge.
+            T::MultiBaseToken::unstake(
+                &candidate.pathfinder,
+                &(APP_ID, target),
+                &Self::candidate_staking(candidate.has_challenge),
+            )?;
+        }
+        <ResultHashsSets<T>>::remove_all();
+        <Paths<T>>::remove_all();
+        <MissedPaths<T>>::remove_all();
+        <ScoreList<T>>::kill();
+        <SeedsConfirmed<T>>::kill();
+        <StartedAt<T>>::kill();
+        if let Some(seeds) = <PreviousSeeds<T>>::take() {
+            T::SeedsBase::restore_seeds(seeds)?;
+            T::SeedsBase::undo_drop_inactive();
+        }
+        Ok(())
+    }
+}
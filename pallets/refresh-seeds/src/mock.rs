@@ -20,10 +20,11 @@ use sp_runtime::{
 };
 use frame_support::{construct_runtime, parameter_types, traits::GenesisBuild};
 use frame_system as system;
+use frame_system::EnsureRoot;
 use orml_currencies::BasicCurrencyAdapter;
 use orml_traits::parameter_type_with_key;
 use sp_core::H256;
-use sp_runtime::Perbill;
+use sp_runtime::{ModuleId, Perbill};
 use zd_primitives::Balance;
 
 pub type Amount = i128;
@@ -105,9 +106,21 @@ parameter_types! {
     pub const MaxTrustCount: u32 = 600;
 }
 
+parameter_types! {
+    pub const MaxSeeds: u32 = 500;
+    pub const MaxSeedTerms: u32 = 2;
+    pub const SeedCooldown: u32 = 1;
+    pub const MaxMissedHeartbeats: u32 = 0;
+}
+
 impl zd_seeds::Config for Test {
     type Event = Event;
     type Reputation = ZdReputation;
+    type UpdateOrigin = EnsureRoot<AccountId>;
+    type MaxSeeds = MaxSeeds;
+    type MaxSeedTerms = MaxSeedTerms;
+    type SeedCooldown = SeedCooldown;
+    type MaxMissedHeartbeats = MaxMissedHeartbeats;
 }
 
 impl zd_trust::Config for Test {
@@ -119,8 +132,19 @@ impl zd_trust::Config for Test {
 	type WeightInfo = ();
 }
 
+parameter_types! {
+    pub const WatchDeposit: Balance = 100;
+    pub const MaxWatches: u32 = 2;
+}
+
 impl zd_reputation::Config for Test {
     type Event = Event;
+    type UpdateOrigin = EnsureRoot<AccountId>;
+    type OnRoundAborted = ZdRefreshSeeds;
+    type OnThresholdCrossed = ();
+    type Currency = Balances;
+    type WatchDeposit = WatchDeposit;
+    type MaxWatches = MaxWatches;
 }
 
 parameter_types! {
@@ -128,6 +152,7 @@ parameter_types! {
     pub const SeedChallengeAmount: Balance = SEED_CHALLENGE_AMOUNT;
     pub const SeedReservStaking: Balance = SEED_RESERV_STAKING;
 	pub const MaxSeedCount: u32 = 2;
+    pub const MaxCandidates: u32 = 4;
 }
 
 impl zd_refresh_seeds::Config for Test {
@@ -139,7 +164,9 @@ impl zd_refresh_seeds::Config for Test {
 	type MultiBaseToken = ZdToken;
 	type SeedStakingAmount = SeedStakingAmount;
 	type MaxSeedCount = MaxSeedCount;
+    type MaxCandidates = MaxCandidates;
     type ConfirmationPeriod = ConfirmationPeriod;
+    type RefSeedTimeOut = RefSeedTimeOut;
     type SeedChallengeAmount = SeedChallengeAmount;
     type SeedReservStaking = SeedReservStaking;
     type WeightInfo = ();
@@ -148,6 +175,8 @@ impl zd_refresh_seeds::Config for Test {
 parameter_types! {
     /// The reputation must be refreshed within this time period.
     pub const RefRepuTiomeOut: BlockNumber = 14_400;
+    /// The seeds must be refreshed within this time period.
+    pub const RefSeedTimeOut: BlockNumber = 14_400;
     /// Amount needed for staking when refreshing reputation and seeds.
     pub const UpdateStakingAmount: Balance = 1_000_000_000;
 	/// Response time period of challenge system.
@@ -176,7 +205,14 @@ impl orml_tokens::Config for Test {
 }
 
 parameter_types! {
-    pub const SocialPoolAccountId: AccountId = 10000;
+    pub const ZdTokenModuleId: ModuleId = ModuleId(*b"zd/socpl");
+    pub const StakingMode: zd_tokens::StakingMode = zd_tokens::StakingMode::Transfer;
+    pub const SolvencyCheckPeriod: u64 = 0;
+    pub const VestingPeriod: u64 = 0;
+    pub const DemurrageRate: Perbill = Perbill::from_percent(0);
+    pub const DemurragePeriod: u64 = 0;
+    pub const MaxBatch: u32 = 100;
+    pub SocialCurrencies: Vec<CurrencyId> = vec![ORS];
 }
 
 impl zd_tokens::Config for Test {
@@ -184,9 +220,19 @@ impl zd_tokens::Config for Test {
     type CurrencyId = CurrencyId;
     type WeightInfo = ();
     type Currency = Currencies;
-    type SocialPool = SocialPoolAccountId;
+    type ModuleId = ZdTokenModuleId;
+    type UpdateOrigin = EnsureRoot<AccountId>;
+    type StakingMode = StakingMode;
+    type SolvencyCheckPeriod = SolvencyCheckPeriod;
+    type VestingPeriod = VestingPeriod;
+    type DemurrageRate = DemurrageRate;
+    type DemurragePeriod = DemurragePeriod;
+    type TrustBase = ZdTrust;
+    type MaxTrustCount = MaxTrustCount;
     type Amount = Amount;
     type BaceToken = BaceToken;
+    type SocialCurrencies = SocialCurrencies;
+    type MaxBatch = MaxBatch;
 }
 impl system::Config for Test {
     type BaseCallFilter = ();
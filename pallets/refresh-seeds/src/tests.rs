@@ -19,6 +19,7 @@
 use super::*;
 use crate::mock::{Event, *};
 use frame_support::{assert_noop, assert_ok};
+use zd_primitives::SeedOrigin;
 
 fn new_test_ext() -> sp_io::TestExternalities {
     let mut ext = ExtBuilder::default().build();
@@ -121,6 +122,10 @@ fn add_should_work() {
 
         let new_free_balance = ZdToken::free_balance(&PATHFINDER);
         assert_eq!(free_balance - new_free_balance, SeedStakingAmount::get());
+        assert_eq!(
+            ZdToken::stakes_of(&PATHFINDER),
+            vec![((APP_ID, A), SeedStakingAmount::get())]
+        );
 
         assert_eq!(
             Candidate {
@@ -136,6 +141,14 @@ fn add_should_work() {
             ZdRefreshSeeds::add(Origin::signed(PATHFINDER), A, 60),
             Error::<Test>::AlreadyExist
         );
+
+        for target in [B, C, D].iter() {
+            assert_ok!(ZdRefreshSeeds::add(Origin::signed(PATHFINDER), *target, 60));
+        }
+        assert_noop!(
+            ZdRefreshSeeds::add(Origin::signed(PATHFINDER), E, 60),
+            Error::<Test>::TooManyCandidates
+        );
     });
 }
 
@@ -414,6 +427,19 @@ fn pathfinder_win() {
         pathfinder_balance += SeedStakingAmount::get();
 
         assert_eq!(ZdToken::free_balance(&PATHFINDER), pathfinder_balance);
+        assert!(ZdToken::stakes_of(&PATHFINDER).is_empty());
+
+        assert_eq!(
+            ZdSeeds::seed_info(&C),
+            Some(SeedInfo {
+                nonce: 1,
+                score: 100,
+                pathfinder: Some(PATHFINDER),
+                origin: SeedOrigin::Computed,
+            })
+        );
+        assert!(ZdSeeds::seeds_at(1).contains(&C));
+        assert_eq!(ZdSeeds::seeds_at(1), ZdSeeds::get_seeds().0);
     });
 }
 
@@ -568,6 +594,17 @@ fn evidence_of_shorter_test() {
 
         let event = Event::zd_refresh_seeds(crate::Event::ShorterPresented(CHALLENGER, B, 0));
         assert!(System::events().iter().any(|record| record.event == event));
+
+        // The challenger takes over the stake of the candidate, the challenged part
+        // is settled with the challenge.
+        assert_eq!(
+            ZdToken::staked_for(&PATHFINDER, &(APP_ID, B)),
+            SeedChallengeAmount::get()
+        );
+        assert_eq!(
+            ZdToken::staked_for(&CHALLENGER, &(APP_ID, B)),
+            SeedReservStaking::get() + ChallengeStakingAmount::get()
+        );
     });
 }
 
@@ -1017,3 +1054,128 @@ fn missed_at_paths_test() {
         ));
     });
 }
+
+#[test]
+fn abort_round_should_work() {
+    new_test_ext().execute_with(|| {
+        let total_balance = ZdToken::free_balance(&PATHFINDER) + ZdToken::free_balance(&CHALLENGER);
+        init_graph(150);
+        assert_ok!(ZdRefreshSeeds::add(Origin::signed(PATHFINDER), C, 12));
+        assert_ok!(ZdRefreshSeeds::challenge(Origin::signed(CHALLENGER), B, 50,));
+        let info = SeedInfo {
+            nonce: 0,
+            score: 30,
+            pathfinder: Some(PATHFINDER),
+            origin: SeedOrigin::Computed,
+        };
+        <PreviousSeeds<Test>>::put(vec![(A, info.clone())]);
+
+        assert_ok!(ZdReputation::abort_round(Origin::root()));
+
+        assert_eq!(
+            ZdToken::free_balance(&PATHFINDER) + ZdToken::free_balance(&CHALLENGER),
+            total_balance
+        );
+        assert!(!<Candidates<Test>>::contains_key(B));
+        assert!(!<Candidates<Test>>::contains_key(C));
+        assert!(ZdRefreshSeeds::get_score_list().is_empty());
+        assert_eq!(ZdRefreshSeeds::previous_seeds(), None);
+        assert!(ZdSeeds::is_seed(&A));
+        assert_eq!(ZdSeeds::seed_info(&A), Some(info));
+        assert!(ZdReputation::is_step(&TIRStep::Free));
+        assert!(Challenges::is_all_harvest(&APP_ID));
+        assert!(ZdToken::stakes_of(&PATHFINDER).is_empty());
+        assert!(ZdToken::stakes_of(&CHALLENGER).is_empty());
+    });
+}
+
+#[test]
+fn skip_seeds_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(ZdSeeds::add_seed(&A));
+        assert_noop!(
+            ZdRefreshSeeds::skip_seeds(Origin::signed(PATHFINDER)),
+            Error::<Test>::StepNotMatch
+        );
+        assert_ok!(ZdRefreshSeeds::start(Origin::signed(PATHFINDER)));
+        assert_noop!(
+            ZdRefreshSeeds::skip_seeds(Origin::signed(PATHFINDER)),
+            Error::<Test>::NotYetTimedOut
+        );
+
+        System::set_block_number(RefSeedTimeOut::get() + 2);
+        assert_ok!(ZdRefreshSeeds::skip_seeds(Origin::signed(PATHFINDER)));
+
+        assert!(ZdSeeds::is_seed(&A));
+        assert_eq!(ZdSeeds::seeds_at(1), vec![A]);
+        assert!(ZdReputation::is_step(&TIRStep::Reputation));
+        let event = Event::zd_refresh_seeds(crate::Event::SeedsKept(PATHFINDER, 1));
+        assert!(System::events().iter().any(|record| record.event == event));
+    });
+}
+
+#[test]
+fn skip_seeds_should_drop_ineligible_seeds() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(ZdSeeds::add_seed(&A));
+        assert_ok!(ZdSeeds::add_seed(&B));
+        assert_ok!(ZdRefreshSeeds::start(Origin::signed(PATHFINDER)));
+        zd_reputation::SystemInfo::<Test>::mutate(|info| info.nonce = 3);
+        let term = zd_seeds::SeedTerm { first: 1, last: 2 };
+        zd_seeds::SeedTerms::<Test>::insert(A, term);
+        let kept = ZdSeeds::get_seed_infos();
+
+        System::set_block_number(RefSeedTimeOut::get() + 2);
+        assert_ok!(ZdRefreshSeeds::skip_seeds(Origin::signed(PATHFINDER)));
+        assert!(!ZdSeeds::is_seed(&A));
+        assert_eq!(ZdSeeds::seeds_at(3), vec![B]);
+        assert_eq!(ZdSeeds::seed_term(&A), Some(term));
+        let event = Event::zd_refresh_seeds(crate::Event::SeedsKept(PATHFINDER, 1));
+        assert!(System::events().iter().any(|record| record.event == event));
+
+        // Dropped seeds are restored if the round is aborted.
+        assert_ok!(ZdReputation::abort_round(Origin::root()));
+        assert_eq!(ZdSeeds::get_seed_infos(), kept);
+    });
+}
+
+#[test]
+fn skip_seeds_should_fail_with_candidates() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(ZdRefreshSeeds::start(Origin::signed(PATHFINDER)));
+        assert_ok!(ZdRefreshSeeds::add(Origin::signed(PATHFINDER), A, 60));
+        System::set_block_number(RefSeedTimeOut::get() + 2);
+        assert_noop!(
+            ZdRefreshSeeds::skip_seeds(Origin::signed(PATHFINDER)),
+            Error::<Test>::StillCandidates
+        );
+    });
+}
+
+#[test]
+fn term_limit_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(ZdRefreshSeeds::start(Origin::signed(PATHFINDER)));
+        zd_reputation::SystemInfo::<Test>::mutate(|info| info.nonce = 3);
+        let term = zd_seeds::SeedTerm { first: 1, last: 2 };
+        zd_seeds::SeedTerms::<Test>::insert(A, term);
+        assert_noop!(
+            ZdRefreshSeeds::add(Origin::signed(PATHFINDER), A, 60),
+            Error::<Test>::TermLimitReached
+        );
+        assert_ok!(ZdRefreshSeeds::add(Origin::signed(PATHFINDER), B, 50));
+        assert_ok!(ZdRefreshSeeds::add(Origin::signed(PATHFINDER), C, 100));
+
+        // `C` reached the limit after being added.
+        zd_seeds::SeedTerms::<Test>::insert(C, term);
+        System::set_block_number(ConfirmationPeriod::get() + 2);
+        assert_ok!(ZdRefreshSeeds::harvest_seed(Origin::signed(PATHFINDER), C));
+        let event = Event::zd_refresh_seeds(crate::Event::SeedsSelected(1));
+        assert!(System::events().iter().any(|record| record.event == event));
+        assert_ok!(ZdRefreshSeeds::harvest_seed(Origin::signed(PATHFINDER), B));
+
+        assert!(!ZdSeeds::is_seed(&C));
+        assert!(ZdSeeds::is_seed(&B));
+        assert!(ZdReputation::is_step(&TIRStep::Reputation));
+    });
+}
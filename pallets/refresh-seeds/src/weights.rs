@@ -47,6 +47,7 @@ pub trait WeightInfo {
     fn invalid_evidence() -> Weight;
     fn harvest_challenge() -> Weight;
     fn harvest_seed() -> Weight;
+    fn skip_seeds() -> Weight;
 }
 
 /// Weights for zd_refresh_seeds using the Substrate node and recommended hardware.
@@ -158,6 +159,12 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(12 as Weight))
             .saturating_add(T::DbWeight::get().writes(9 as Weight))
     }
+    #[cfg(not(tarpaulin_include))]
+    fn skip_seeds() -> Weight {
+        (45_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
 }
 
 // For backwards compatibility and tests
@@ -268,4 +275,10 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(12 as Weight))
             .saturating_add(RocksDbWeight::get().writes(9 as Weight))
     }
+    #[cfg(not(tarpaulin_include))]
+    fn skip_seeds() -> Weight {
+        (45_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
 }
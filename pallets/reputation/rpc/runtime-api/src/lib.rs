@@ -0,0 +1,48 @@
+// Copyright 2021 ZeroDAO
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime API definition for the reputation module.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::too_many_arguments)]
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::Codec;
+use sp_core::H256;
+use sp_runtime::Permill;
+use sp_std::vec::Vec;
+use zd_primitives::merkle::ReputationProof;
+
+sp_api::decl_runtime_apis! {
+    #[api_version(2)]
+    pub trait ReputationApi<AccountId> where
+        AccountId: Codec,
+    {
+        /// Merkle root of the reputation of round `nonce`, if finalised.
+        fn reputation_root(nonce: u32) -> Option<H256>;
+
+        /// Proof of the reputation of `who` in the latest finalised round.
+        fn reputation_proof(who: AccountId) -> Option<ReputationProof>;
+
+        /// Proof of the reputation of `who` in the finalised round `nonce`, if the
+        /// round is still stored.
+        fn reputation_proof_at(who: AccountId, nonce: u32) -> Option<ReputationProof>;
+
+        /// Share of accounts of the latest finalised round ranked below `who`.
+        fn percentile(who: AccountId) -> Option<Permill>;
+
+        /// Highest reputation values of the latest finalised round.
+        fn leaderboard() -> Vec<(AccountId, u32)>;
+    }
+}
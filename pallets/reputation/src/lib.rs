@@ -26,12 +26,65 @@
 //!  - `Reputation` -  Provides the ability to obtain and modify user
 //! reputation values and to obtain and record system status.
 //!
+//! ### Reputation root
+//!
+//! When a round is finalised, a Merkle root over the `(AccountId, score)` of
+//! every account refreshed in that round, at most `MAX_ROUND_ACCOUNTS`, is
+//! stored under its nonce at the start of the next block. Round finalisation
+//! only happens once all challenges have timed out, so the committed scores
+//! are the ones verified by the challenge game. Proofs for the latest
+//! finalised round can be generated with `reputation_proof`, and for an older
+//! one with `reputation_proof_at` while it is still stored.
+//!
+//! The accounts and reputation of a round are normally kept for two rounds.
+//! Pallets that need them for longer, like referenda weighted by the
+//! reputation of a round, pin the round with `pin_round` until they are done.
+//!
+//! ### Leaderboard
+//!
+//! Each round keeps the `LEADERBOARD_SIZE` highest reputation values and a
+//! histogram of all values, both updated as reputation is refreshed or
+//! corrected and frozen once the round is finalised. When a lowered value
+//! leaves room on a board that accounts were left off, the board is refilled
+//! from the scores of the round, so it always lists the highest values of
+//! `LEADERBOARD_SIZE` accounts, or of all of them if fewer. `percentile` ranks
+//! an account against the latest finalised round.
+//!
+//! ### Thresholds
+//!
+//! Pallets and accounts can watch reputation thresholds, at most
+//! `MAX_THRESHOLDS` of them. Accounts reserve `WatchDeposit` for each threshold
+//! they watch, up to `MaxWatches`, and get it back when they stop watching it.
+//! Once a round is finalised, every account whose reputation moved across a
+//! watched threshold, including accounts of the previous round that were not
+//! refreshed, is reported with a `ThresholdCrossed` event and to
+//! `OnThresholdCrossed`. The accounts to check are queued in batches of
+//! `NOTIFY_BATCH` in the next block and one batch is checked per block, no new
+//! round starts before all are checked. Unfinalised values are not reported as
+//! they may still be challenged.
+//!
+//! ### Provisional reputation
+//!
+//! Accounts with a computed reputation can vouch for newcomers who have none,
+//! spending up to `VOUCH_SHARE` of their own reputation per round. The spent
+//! reputation is taken off the computed reputation of the voucher until the
+//! next round is finalised, the finalised values, their root and rankings are
+//! kept as they are. The newcomer gets the vouched amount as a provisional
+//! reputation, at most `MAX_PROVISIONAL_SCORE`, until the next round is
+//! finalised or a computed reputation replaces it. `get_reputation` tells both
+//! kinds apart.
+//!
 //! ## Interface
 //!
 //! ### Dispatchable Functions
 //!
 //! - `set_period` - Setting the system update interval to a given number
-//! of blocks requires administrator privileges.
+//! of blocks, requires `UpdateOrigin`.
+//! - `abort_round` - Abort the round in progress, restoring the reputation of
+//! the previous round, requires `UpdateOrigin`.
+//! - `watch` - Watch a reputation threshold, reserving `WatchDeposit`.
+//! - `unwatch` - Stop watching a reputation threshold, releasing the deposit.
+//! - `vouch` - Give part of one's reputation to a newcomer as provisional reputation.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
@@ -39,16 +92,26 @@
 use frame_support::{
     codec::{Decode, Encode},
     ensure, pallet,
-    traits::Get,
-    transactional, RuntimeDebug,
+    traits::{Currency, Get, ReservableCurrency},
+    transactional,
+    weights::Weight,
+    RuntimeDebug,
 };
 use frame_system::{self as system};
-use sp_runtime::{traits::Zero, DispatchResult};
-use zd_primitives::TIRStep;
-use zd_support::Reputation;
+use sp_core::H256;
+use sp_runtime::{traits::Zero, DispatchError, DispatchResult, Permill};
+use sp_std::vec::Vec;
+use zd_primitives::{
+    merkle::{leaf_hash, merkle_proof, merkle_root, verify_proof, ReputationProof},
+    ReputationValue, TIRStep,
+};
+use zd_support::{OnRoundAborted, OnThresholdCrossed, Reputation};
 
 pub use pallet::*;
 
+type BalanceOf<T> =
+    <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
 #[cfg(test)]
 mod mock;
 #[cfg(test)]
@@ -58,6 +121,28 @@ mod tests;
 pub const MAX_SEED: usize = 500;
 /// Seed user initializes reputation values
 pub const INIT_SEED_RANK: usize = 1000;
+/// Maximum number of accounts refreshed in a round.
+pub const MAX_ROUND_ACCOUNTS: usize = 10_000;
+/// Weight of hashing an account into the reputation root.
+pub const ROOT_LEAF_WEIGHT: Weight = 1_000_000;
+/// Maximum number of accounts on the leaderboard of a round.
+pub const LEADERBOARD_SIZE: usize = 100;
+/// Range of reputation values counted by each histogram bucket.
+pub const SCORE_BUCKET_WIDTH: u32 = 100;
+/// Number of histogram buckets, the last one also counts all higher values.
+pub const SCORE_BUCKETS: usize = 100;
+/// Maximum number of watched reputation thresholds.
+pub const MAX_THRESHOLDS: usize = 32;
+/// Number of accounts checked for threshold crossings in a block.
+pub const NOTIFY_BATCH: usize = 500;
+/// Weight of reporting a threshold crossing.
+pub const CROSSING_WEIGHT: Weight = 1_000_000;
+/// Weight of decoding and queueing an account for threshold checks.
+pub const QUEUE_ACCOUNT_WEIGHT: Weight = 100_000;
+/// Maximum provisional reputation of an account.
+pub const MAX_PROVISIONAL_SCORE: u32 = 100;
+/// Share of their reputation accounts can vouch for others in each round.
+pub const VOUCH_SHARE: Permill = Permill::from_percent(10);
 
 /// The state of the entire reputation system.
 #[derive(Encode, Decode, Clone, PartialEq, Default, Eq, RuntimeDebug)]
@@ -91,16 +176,48 @@ pub struct ReputationScore {
     pub nonce: u32,
 }
 
+/// Reputation vouched for an account without a computed one.
+#[derive(Encode, Decode, Clone, PartialEq, Default, Eq, RuntimeDebug)]
+pub struct ProvisionalScore {
+    /// Reputation value.
+    pub score: u32,
+
+    /// The finalised round the reputation was vouched in, it expires with the
+    /// next one.
+    pub nonce: u32,
+}
+
 #[pallet]
 pub mod pallet {
     use super::*;
 
     use frame_support::{dispatch::DispatchResultWithPostInfo, pallet_prelude::*};
-    use frame_system::pallet_prelude::*;
+    use frame_system::{ensure_signed, pallet_prelude::*};
 
     #[pallet::config]
     pub trait Config: frame_system::Config {
         type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+        /// The origin which may update the system parameters.
+        type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+        /// Handler for rolling back the state of the refreshing pallets when a
+        /// round is aborted.
+        type OnRoundAborted: OnRoundAborted;
+
+        /// Handler for finalised reputation values crossing a watched threshold.
+        type OnThresholdCrossed: OnThresholdCrossed<Self::AccountId>;
+
+        /// The currency in which watch deposits are reserved.
+        type Currency: ReservableCurrency<Self::AccountId>;
+
+        /// The deposit reserved for each threshold an account watches.
+        #[pallet::constant]
+        type WatchDeposit: Get<BalanceOf<Self>>;
+
+        /// Maximum number of thresholds an account can watch.
+        #[pallet::constant]
+        type MaxWatches: Get<u32>;
     }
 
     #[pallet::pallet]
@@ -117,6 +234,120 @@ pub mod pallet {
     pub type ReputationScores<T: Config> =
         StorageMap<_, Twox64Concat, T::AccountId, [ReputationScore; 2], ValueQuery>;
 
+    /// The reputation of `AccountId` the refresh in round `nonce` dropped from
+    /// `ReputationScores`, restored if the round is aborted. Only the current
+    /// round is kept.
+    #[pallet::storage]
+    #[pallet::getter(fn superseded)]
+    pub type Superseded<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        u32,
+        Twox64Concat,
+        T::AccountId,
+        ReputationScore,
+        OptionQuery,
+    >;
+
+    /// Accounts refreshed in round `nonce`, in the order of refreshing, at most
+    /// `MAX_ROUND_ACCOUNTS`. Only the current and the previous round are kept,
+    /// along with pinned rounds.
+    #[pallet::storage]
+    #[pallet::getter(fn round_accounts)]
+    pub type RoundAccounts<T: Config> =
+        StorageMap<_, Twox64Concat, u32, Vec<T::AccountId>, ValueQuery>;
+
+    /// Number of pins keeping the accounts and reputation of round `nonce`.
+    #[pallet::storage]
+    #[pallet::getter(fn pins)]
+    pub type Pins<T: Config> = StorageMap<_, Twox64Concat, u32, u32, ValueQuery>;
+
+    /// The reputation of `AccountId` in the pinned round `nonce`, once a later
+    /// refresh has dropped it from `ReputationScores`.
+    #[pallet::storage]
+    #[pallet::getter(fn pinned_score)]
+    pub type PinnedScores<T: Config> =
+        StorageDoubleMap<_, Twox64Concat, u32, Twox64Concat, T::AccountId, u32, OptionQuery>;
+
+    /// Merkle root of the reputation finalised in round `nonce`.
+    #[pallet::storage]
+    #[pallet::getter(fn reputation_root)]
+    pub type ReputationRoots<T: Config> = StorageMap<_, Twox64Concat, u32, H256, OptionQuery>;
+
+    /// The finalised round whose reputation root is stored at the start of the
+    /// next block.
+    #[pallet::storage]
+    #[pallet::getter(fn finalizing)]
+    pub type Finalizing<T: Config> = StorageValue<_, u32, OptionQuery>;
+
+    /// The finalised round whose threshold crossings are being reported, and
+    /// the next batch of its accounts to check.
+    #[pallet::storage]
+    #[pallet::getter(fn notifying)]
+    pub type Notifying<T: Config> = StorageValue<_, (u32, u32), OptionQuery>;
+
+    /// The highest reputation values of round `nonce` in descending order, at
+    /// most `LEADERBOARD_SIZE`. Frozen once the round is finalised, only the
+    /// current and the previous round are kept.
+    #[pallet::storage]
+    #[pallet::getter(fn leaderboard)]
+    pub type Leaderboards<T: Config> =
+        StorageMap<_, Twox64Concat, u32, Vec<(T::AccountId, u32)>, ValueQuery>;
+
+    /// The highest reputation value of round `nonce` left off its leaderboard.
+    /// Only higher values are added to the tail of the board.
+    #[pallet::storage]
+    #[pallet::getter(fn leaderboard_cut)]
+    pub type LeaderboardCuts<T: Config> = StorageMap<_, Twox64Concat, u32, u32, OptionQuery>;
+
+    /// Number of accounts in each `SCORE_BUCKET_WIDTH` range of reputation
+    /// values of round `nonce`. Kept along with `Leaderboards`.
+    #[pallet::storage]
+    #[pallet::getter(fn histogram)]
+    pub type Histograms<T: Config> = StorageMap<_, Twox64Concat, u32, Vec<u32>, ValueQuery>;
+
+    /// Watched reputation thresholds in ascending order, with the number of
+    /// watchers of each.
+    #[pallet::storage]
+    #[pallet::getter(fn thresholds)]
+    pub type Thresholds<T: Config> = StorageValue<_, Vec<(u32, u32)>, ValueQuery>;
+
+    /// Provisional reputation of `AccountId`.
+    #[pallet::storage]
+    #[pallet::getter(fn provisional)]
+    pub type Provisionals<T: Config> =
+        StorageMap<_, Twox64Concat, T::AccountId, ProvisionalScore, OptionQuery>;
+
+    /// The finalised round `AccountId` last vouched in, and the reputation it
+    /// vouched in that round.
+    #[pallet::storage]
+    #[pallet::getter(fn vouched)]
+    pub type Vouched<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, (u32, u32), ValueQuery>;
+
+    /// The deposit reserved by `AccountId` for watching the threshold.
+    #[pallet::storage]
+    #[pallet::getter(fn watching)]
+    pub type Watching<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        T::AccountId,
+        Twox64Concat,
+        u32,
+        BalanceOf<T>,
+        OptionQuery,
+    >;
+
+    /// Number of thresholds `AccountId` watches.
+    #[pallet::storage]
+    #[pallet::getter(fn watch_count)]
+    pub type WatchCount<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, u32, ValueQuery>;
+
+    /// Batches of accounts still to be checked for threshold crossings.
+    #[pallet::storage]
+    #[pallet::getter(fn notify_queue)]
+    pub type NotifyQueue<T: Config> =
+        StorageMap<_, Twox64Concat, u32, Vec<T::AccountId>, ValueQuery>;
+
     /// Initializes a `period` to the given value.
     #[pallet::genesis_config]
     pub struct GenesisConfig<T: Config> {
@@ -146,6 +377,24 @@ pub mod pallet {
     pub enum Event<T: Config> {
         /// Updated period. \[period\]
         UpdatedPeriod(T::BlockNumber),
+        /// A new round of refreshing has started. \[nonce\]
+        RoundStarted(u32),
+        /// The system moved to another step. \[nonce, from, to\]
+        StepChanged(u32, TIRStep, TIRStep),
+        /// The reputation of the round is finalised. \[nonce, block\]
+        RoundFinalized(u32, T::BlockNumber),
+        /// The reputation root of the round is stored. \[nonce, root\]
+        ReputationRootStored(u32, H256),
+        /// The round in progress was aborted. \[nonce\]
+        RoundAborted(u32),
+        /// A threshold is watched. \[who, threshold\]
+        ThresholdWatched(T::AccountId, u32),
+        /// A threshold is no longer watched. \[who, threshold\]
+        ThresholdUnwatched(T::AccountId, u32),
+        /// A finalised reputation crossed a watched threshold. \[who, threshold, old, new\]
+        ThresholdCrossed(T::AccountId, u32, u32, u32),
+        /// Provisional reputation was vouched. \[voucher, target, score\]
+        Vouched(T::AccountId, T::AccountId, u32),
     }
 
     #[pallet::error]
@@ -162,27 +411,183 @@ pub mod pallet {
         ChallengeNotOverYet,
         /// Too short an interval between renewal periods.
         TooShortAnInterval,
+        /// No round is in progress.
+        NotInRound,
+        /// Thresholds must be greater than zero.
+        ZeroThreshold,
+        /// Maximum number of watched thresholds reached.
+        TooManyThresholds,
+        /// The threshold is already watched.
+        AlreadyWatching,
+        /// The threshold is not watched.
+        NotWatching,
+        /// Maximum number of thresholds watched by the account reached.
+        TooManyWatches,
+        /// Only accounts with a computed reputation can vouch.
+        NoReputation,
+        /// The target already has a computed reputation.
+        AlreadyReputable,
+        /// Accounts cannot vouch for themselves.
+        SelfVouch,
+        /// Vouching more than `VOUCH_SHARE` of the reputation in a round.
+        VouchLimitExceeded,
+        /// The provisional reputation of the target is at its maximum.
+        ProvisionalScoreCapped,
+        /// Maximum number of accounts refreshed in a round reached.
+        TooManyAccounts,
+        /// Threshold crossings of the last round are still being reported.
+        StillNotifying,
     }
 
     #[pallet::hooks]
-    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(_now: T::BlockNumber) -> Weight {
+            let root_weight = match Finalizing::<T>::take() {
+                Some(nonce) => {
+                    let queue_weight = match Self::notifying() {
+                        Some(_) => Self::queue_crossings(nonce),
+                        None => T::DbWeight::get().reads(1),
+                    };
+                    Self::store_root(nonce).saturating_add(queue_weight)
+                }
+                None => T::DbWeight::get().reads(1),
+            };
+            let notify_weight = match Self::notifying() {
+                Some((nonce, batch)) => Self::notify_crossings(nonce, batch),
+                None => T::DbWeight::get().reads(1),
+            };
+            root_weight.saturating_add(notify_weight)
+        }
+    }
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         /// Set the system update interval to the given number of blocks.
         ///
-        /// The dispatch origin for this call must be `Signed` by the root.
+        /// The dispatch origin for this call must be `UpdateOrigin`.
         #[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
         #[transactional]
         pub fn set_period(
             origin: OriginFor<T>,
             period: T::BlockNumber,
         ) -> DispatchResultWithPostInfo {
-            ensure_root(origin)?;
+            T::UpdateOrigin::ensure_origin(origin)?;
             Self::do_set_period(period)?;
             Self::deposit_event(Event::UpdatedPeriod(period));
             Ok(().into())
         }
+
+        /// Abort the round in progress and return to the `Free` step.
+        ///
+        /// Reputation refreshed in the round is discarded and the values of the
+        /// previous round are restored. Pending seeds, refreshes and challenges of
+        /// the round are settled by `OnRoundAborted`. A new round can be started
+        /// right away.
+        ///
+        /// This settles everything staked in the round, at most `MAX_ROUND_ACCOUNTS`
+        /// refreshes and their challenges, so it takes the whole block.
+        ///
+        /// The dispatch origin for this call must be `UpdateOrigin`.
+        #[pallet::weight((Pallet::<T>::abort_round_weight(), DispatchClass::Operational))]
+        #[transactional]
+        pub fn abort_round(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+            T::UpdateOrigin::ensure_origin(origin)?;
+            let operation_status = Self::system_info();
+            ensure!(
+                operation_status.step != TIRStep::Free,
+                Error::<T>::NotInRound
+            );
+            T::OnRoundAborted::on_round_aborted()?;
+            let nonce = operation_status.nonce;
+            Self::discard_round(nonce);
+            let now = Self::now();
+            SystemInfo::<T>::mutate(|operation_status| {
+                operation_status.nonce = nonce - 1;
+                operation_status.next = now;
+                operation_status.step = TIRStep::Free;
+            });
+            Self::deposit_event(Event::StepChanged(
+                nonce,
+                operation_status.step,
+                TIRStep::Free,
+            ));
+            Self::deposit_event(Event::RoundAborted(nonce));
+            Ok(().into())
+        }
+
+        /// Watch the reputation `threshold`, crossings of it will be reported once
+        /// rounds are finalised.
+        ///
+        /// `WatchDeposit` is reserved until the threshold is unwatched.
+        ///
+        /// The dispatch origin for this call must be `Signed`.
+        #[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4,4))]
+        #[transactional]
+        pub fn watch(origin: OriginFor<T>, threshold: u32) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::do_watch(&who, threshold, T::WatchDeposit::get())?;
+            Ok(().into())
+        }
+
+        /// Vouch for `target` with `score` of the caller's reputation.
+        ///
+        /// `target` must have no computed reputation. Its provisional reputation is
+        /// raised by `score`, at most to `MAX_PROVISIONAL_SCORE`, and the caller only
+        /// spends what was added, which is taken off its computed reputation.
+        ///
+        /// The dispatch origin for this call must be `Signed`.
+        #[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4,2))]
+        #[transactional]
+        pub fn vouch(
+            origin: OriginFor<T>,
+            target: T::AccountId,
+            score: u32,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(who != target, Error::<T>::SelfVouch);
+            let voucher_score = Self::finalized_score(&who).ok_or(Error::<T>::NoReputation)?;
+            ensure!(
+                Self::computed_reputation(&target).is_none(),
+                Error::<T>::AlreadyReputable
+            );
+            let nonce = Self::finalized_nonce();
+            let current = Self::provisional_reputation(&target).unwrap_or_default();
+            let added = score.min(MAX_PROVISIONAL_SCORE.saturating_sub(current));
+            ensure!(added > 0, Error::<T>::ProvisionalScoreCapped);
+            Vouched::<T>::try_mutate(&who, |vouched| -> DispatchResult {
+                let spent = match vouched.0 == nonce {
+                    true => vouched.1,
+                    false => 0,
+                };
+                let spent = spent.saturating_add(added);
+                ensure!(
+                    spent <= VOUCH_SHARE.mul_floor(voucher_score),
+                    Error::<T>::VouchLimitExceeded
+                );
+                *vouched = (nonce, spent);
+                Ok(())
+            })?;
+            Provisionals::<T>::insert(
+                &target,
+                ProvisionalScore {
+                    score: current + added,
+                    nonce,
+                },
+            );
+            Self::deposit_event(Event::Vouched(who, target, added));
+            Ok(().into())
+        }
+
+        /// Stop watching the reputation `threshold`, releasing its deposit.
+        ///
+        /// The dispatch origin for this call must be `Signed`.
+        #[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4,4))]
+        #[transactional]
+        pub fn unwatch(origin: OriginFor<T>, threshold: u32) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::do_unwatch(&who, threshold)?;
+            Ok(().into())
+        }
     }
 }
 
@@ -197,6 +602,336 @@ impl<T: Config> Pallet<T> {
         });
     }
 
+    /// The largest weight of an operational extrinsic.
+    pub(crate) fn abort_round_weight() -> Weight {
+        let block_weights = T::BlockWeights::get();
+        block_weights
+            .get(DispatchClass::Operational)
+            .max_extrinsic
+            .unwrap_or(block_weights.max_block)
+    }
+
+    /// Restores the reputation of the accounts refreshed in round `nonce` and
+    /// removes the records of the round.
+    pub(crate) fn discard_round(nonce: u32) {
+        for who in RoundAccounts::<T>::take(nonce).iter() {
+            let superseded = Superseded::<T>::take(nonce, who).unwrap_or_default();
+            ReputationScores::<T>::mutate(who, |irs| {
+                if irs[0].nonce == nonce {
+                    *irs = [irs[1].clone(), superseded];
+                }
+            });
+        }
+        Leaderboards::<T>::remove(nonce);
+        LeaderboardCuts::<T>::remove(nonce);
+        Histograms::<T>::remove(nonce);
+    }
+
+    /// The latest round whose reputation has been finalised.
+    pub fn finalized_nonce() -> u32 {
+        let system_info = Self::system_info();
+        match system_info.step == TIRStep::Free {
+            true => system_info.nonce,
+            false => system_info.nonce.saturating_sub(1),
+        }
+    }
+
+    /// Returns the reputation of `who` in round `nonce`, if it is still stored.
+    pub fn score_at(who: &T::AccountId, nonce: u32) -> Option<u32> {
+        Self::get_ir(who)
+            .iter()
+            .find(|ir| ir.nonce == nonce)
+            .map(|ir| ir.score)
+            .or_else(|| Self::pinned_score(nonce, who))
+    }
+
+    /// Returns the proof of the reputation of `who` in the latest finalised round.
+    pub fn reputation_proof(who: &T::AccountId) -> Option<ReputationProof> {
+        Self::reputation_proof_at(who, Self::finalized_nonce())
+    }
+
+    /// Returns the proof of the reputation of `who` in the finalised round `nonce`,
+    /// if the round is still stored.
+    pub fn reputation_proof_at(who: &T::AccountId, nonce: u32) -> Option<ReputationProof> {
+        if nonce > Self::finalized_nonce() {
+            return None;
+        }
+        let accounts = Self::round_accounts(nonce);
+        let index = accounts.iter().position(|account| account == who)?;
+        let proof = merkle_proof(&Self::round_leaves(nonce, &accounts[..])[..], index)?;
+        Some(ReputationProof {
+            nonce,
+            score: Self::score_at(who, nonce)?,
+            proof,
+        })
+    }
+
+    /// Returns the share of accounts of the latest finalised round with a lower
+    /// reputation than `who`, counting half of those in the same histogram bucket.
+    pub fn percentile(who: &T::AccountId) -> Option<Permill> {
+        let nonce = Self::finalized_nonce();
+        let score = Self::score_at(who, nonce)?;
+        let histogram = Self::histogram(nonce);
+        let bucket = Self::bucket_of(score);
+        let same = *histogram.get(bucket)? as u64;
+        let below = histogram[..bucket]
+            .iter()
+            .fold(0u64, |acc, count| acc + *count as u64);
+        let total = histogram.iter().fold(0u64, |acc, count| acc + *count as u64);
+        Some(Permill::from_rational_approximation(
+            below * 2 + same,
+            total * 2,
+        ))
+    }
+
+    pub(crate) fn bucket_of(score: u32) -> usize {
+        ((score / SCORE_BUCKET_WIDTH) as usize).min(SCORE_BUCKETS - 1)
+    }
+
+    /// Returns the latest verified reputation value of `target`, less what it
+    /// vouched in the round.
+    pub fn computed_reputation(target: &T::AccountId) -> Option<u32> {
+        let score = Self::finalized_score(target)?;
+        let (nonce, spent) = Self::vouched(target);
+        match nonce == Self::finalized_nonce() {
+            true => Some(score.saturating_sub(spent)),
+            false => Some(score),
+        }
+    }
+
+    /// Returns the reputation value of `target` in the latest finalised round.
+    pub(crate) fn finalized_score(target: &T::AccountId) -> Option<u32> {
+        let system_info = Self::system_info();
+        let nonce = system_info.nonce;
+        let irs = Self::get_ir(target);
+        match system_info.step == TIRStep::Free {
+            true => {
+                if irs[0].nonce == nonce {
+                    return Some(irs[0].score);
+                }
+            }
+            false => {
+                // nonce cannot be smaller than 1
+                if irs[0].nonce == nonce - 1 {
+                    return Some(irs[0].score);
+                } else if irs[1].nonce == nonce - 1 {
+                    return Some(irs[1].score);
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns the provisional reputation of `target`, if it has not expired.
+    pub fn provisional_reputation(target: &T::AccountId) -> Option<u32> {
+        Self::provisional(target)
+            .filter(|provisional| provisional.nonce == Self::finalized_nonce())
+            .map(|provisional| provisional.score)
+    }
+
+    /// Adds a watcher to `threshold`, for pallets watching thresholds.
+    pub fn add_threshold(threshold: u32) -> DispatchResult {
+        ensure!(threshold > 0, Error::<T>::ZeroThreshold);
+        Thresholds::<T>::try_mutate(|thresholds| {
+            match thresholds.binary_search_by_key(&threshold, |(t, _)| *t) {
+                Ok(index) => thresholds[index].1 = thresholds[index].1.saturating_add(1),
+                Err(index) => {
+                    ensure!(
+                        thresholds.len() < MAX_THRESHOLDS,
+                        Error::<T>::TooManyThresholds
+                    );
+                    thresholds.insert(index, (threshold, 1));
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Watches `threshold` for `who`, reserving `deposit` from it. Pallets watching
+    /// thresholds on behalf of accounts can pass a zero deposit.
+    #[transactional]
+    pub fn do_watch(who: &T::AccountId, threshold: u32, deposit: BalanceOf<T>) -> DispatchResult {
+        ensure!(
+            !Watching::<T>::contains_key(who, threshold),
+            Error::<T>::AlreadyWatching
+        );
+        WatchCount::<T>::try_mutate(who, |count| -> DispatchResult {
+            ensure!(*count < T::MaxWatches::get(), Error::<T>::TooManyWatches);
+            *count += 1;
+            Ok(())
+        })?;
+        Self::add_threshold(threshold)?;
+        T::Currency::reserve(who, deposit)?;
+        Watching::<T>::insert(who, threshold, deposit);
+        Self::deposit_event(Event::ThresholdWatched(who.clone(), threshold));
+        Ok(())
+    }
+
+    /// Stops watching `threshold` for `who`, releasing the deposit it reserved.
+    pub fn do_unwatch(who: &T::AccountId, threshold: u32) -> DispatchResult {
+        let deposit = Watching::<T>::take(who, threshold).ok_or(Error::<T>::NotWatching)?;
+        WatchCount::<T>::mutate_exists(who, |count| {
+            *count = count.map(|c| c.saturating_sub(1)).filter(|c| *c > 0);
+        });
+        Self::remove_threshold(threshold);
+        T::Currency::unreserve(who, deposit);
+        Self::deposit_event(Event::ThresholdUnwatched(who.clone(), threshold));
+        Ok(())
+    }
+
+    /// Removes a watcher from `threshold`, the threshold is dropped with its last
+    /// watcher.
+    pub fn remove_threshold(threshold: u32) {
+        Thresholds::<T>::mutate(|thresholds| {
+            if let Ok(index) = thresholds.binary_search_by_key(&threshold, |(t, _)| *t) {
+                match thresholds[index].1 > 1 {
+                    true => thresholds[index].1 -= 1,
+                    false => {
+                        thresholds.remove(index);
+                    }
+                }
+            }
+        });
+    }
+
+    /// Queues the accounts to check for threshold crossings in round `nonce` in
+    /// batches of `NOTIFY_BATCH`, the accounts refreshed in the round and those of
+    /// the previous round that were not. Returns the weight used.
+    pub(crate) fn queue_crossings(nonce: u32) -> Weight {
+        let mut accounts = Self::round_accounts(nonce);
+        let previous = Self::round_accounts(nonce.saturating_sub(1));
+        let previous_count = previous.len() as Weight;
+        // Accounts of the previous round that were not refreshed lose their reputation,
+        // the refreshed ones are checked with the round.
+        accounts.extend(
+            previous
+                .into_iter()
+                .filter(|who| Self::score_at(who, nonce).is_none()),
+        );
+        let mut batches: u32 = 0;
+        for (index, batch) in accounts.chunks(NOTIFY_BATCH).enumerate() {
+            batches = index as u32 + 1;
+            NotifyQueue::<T>::insert(index as u32, batch.to_vec());
+        }
+        if batches == 0 {
+            Notifying::<T>::kill();
+        }
+        let count = (accounts.len() as Weight).saturating_add(previous_count);
+        T::DbWeight::get()
+            .reads_writes(previous_count.saturating_add(3), (batches as Weight).saturating_add(1))
+            .saturating_add(count.saturating_mul(QUEUE_ACCOUNT_WEIGHT))
+    }
+
+    /// Reports the watched thresholds crossed by the reputation finalised in
+    /// round `nonce` for the accounts queued in `batch`. Returns the weight used.
+    pub(crate) fn notify_crossings(nonce: u32, batch: u32) -> Weight {
+        let thresholds = Self::thresholds();
+        let accounts = NotifyQueue::<T>::take(batch);
+        let mut crossings: Weight = 0;
+        for who in accounts.iter() {
+            let old = Self::score_at(who, nonce.saturating_sub(1)).unwrap_or_default();
+            let new = Self::score_at(who, nonce).unwrap_or_default();
+            for (threshold, _) in thresholds.iter() {
+                if (old >= *threshold) != (new >= *threshold) {
+                    T::OnThresholdCrossed::on_threshold_crossed(who, *threshold, old, new);
+                    Self::deposit_event(Event::ThresholdCrossed(who.clone(), *threshold, old, new));
+                    crossings = crossings.saturating_add(1);
+                }
+            }
+        }
+        match NotifyQueue::<T>::contains_key(batch.saturating_add(1)) {
+            true => Notifying::<T>::put((nonce, batch.saturating_add(1))),
+            false => Notifying::<T>::kill(),
+        }
+        let count = accounts.len() as Weight;
+        T::DbWeight::get()
+            .reads_writes(count.saturating_mul(2).saturating_add(4), 2)
+            .saturating_add(count.saturating_mul(QUEUE_ACCOUNT_WEIGHT))
+            .saturating_add(crossings.saturating_mul(CROSSING_WEIGHT))
+    }
+
+    /// Records the reputation `score` of `who` in round `nonce`, replacing `old`.
+    pub(crate) fn record_score(nonce: u32, who: &T::AccountId, old: Option<u32>, score: u32) {
+        Histograms::<T>::mutate(nonce, |histogram| {
+            histogram.resize(SCORE_BUCKETS, 0);
+            if let Some(old) = old {
+                let count = &mut histogram[Self::bucket_of(old)];
+                *count = count.saturating_sub(1);
+            }
+            let count = &mut histogram[Self::bucket_of(score)];
+            *count = count.saturating_add(1);
+        });
+        let mut cut = Self::leaderboard_cut(nonce);
+        let refill = Leaderboards::<T>::mutate(nonce, |board| {
+            if let Some(index) = board.iter().position(|(account, _)| account == who) {
+                board.remove(index);
+            }
+            let index = board
+                .iter()
+                .position(|(_, s)| *s < score)
+                .unwrap_or_else(|| board.len());
+            // Accounts left off the board may be above the tail.
+            let listed = index < board.len()
+                || (index < LEADERBOARD_SIZE && cut.map_or(true, |cut| score > cut));
+            let left_off = match listed {
+                true => {
+                    board.insert(index, (who.clone(), score));
+                    match board.len() > LEADERBOARD_SIZE {
+                        true => board.pop().map(|(_, s)| s),
+                        false => None,
+                    }
+                }
+                false => Some(score),
+            };
+            if let Some(score) = left_off {
+                cut = Some(cut.map_or(score, |cut| cut.max(score)));
+            }
+            cut.is_some() && board.len() < LEADERBOARD_SIZE
+        });
+        match refill {
+            true => Self::refill_leaderboard(nonce),
+            false => {
+                if let Some(cut) = cut {
+                    LeaderboardCuts::<T>::insert(nonce, cut);
+                }
+            }
+        }
+    }
+
+    /// Rebuilds the leaderboard of round `nonce` from the scores of its accounts.
+    pub(crate) fn refill_leaderboard(nonce: u32) {
+        let mut scores = Self::round_accounts(nonce)
+            .into_iter()
+            .filter_map(|who| Self::score_at(&who, nonce).map(|score| (who, score)))
+            .collect::<Vec<_>>();
+        scores.sort_by(|(_, a), (_, b)| b.cmp(a));
+        match scores.get(LEADERBOARD_SIZE) {
+            Some((_, cut)) => LeaderboardCuts::<T>::insert(nonce, cut),
+            None => LeaderboardCuts::<T>::remove(nonce),
+        }
+        scores.truncate(LEADERBOARD_SIZE);
+        Leaderboards::<T>::insert(nonce, scores);
+    }
+
+    pub(crate) fn round_leaves(nonce: u32, accounts: &[T::AccountId]) -> Vec<H256> {
+        accounts
+            .iter()
+            .map(|who| leaf_hash(who, Self::score_at(who, nonce).unwrap_or_default()))
+            .collect()
+    }
+
+    /// Stores the reputation root of round `nonce`, returning the weight used.
+    pub(crate) fn store_root(nonce: u32) -> Weight {
+        let accounts = Self::round_accounts(nonce);
+        let root = merkle_root(&Self::round_leaves(nonce, &accounts[..])[..]);
+        ReputationRoots::<T>::insert(nonce, root);
+        Self::deposit_event(Event::ReputationRootStored(nonce, root));
+        let count = accounts.len() as Weight;
+        T::DbWeight::get()
+            .reads_writes(count.saturating_add(2), 2)
+            .saturating_add(count.saturating_mul(ROOT_LEAF_WEIGHT))
+    }
+
     pub(crate) fn do_set_period(period: T::BlockNumber) -> DispatchResult {
         SystemInfo::<T>::try_mutate(|operation_status| {
             ensure!(
@@ -212,11 +947,26 @@ impl<T: Config> Pallet<T> {
 impl<T: Config> Reputation<T::AccountId, T::BlockNumber, TIRStep> for Pallet<T> {
     // Low-level operation. Make changes directly to the latest nonce's REPUTATION
     fn mutate_reputation(target: &T::AccountId, ir: &u32) {
-        ReputationScores::<T>::mutate(&target, |x| x[0].score = *ir);
+        let nonce = Self::system_info().nonce;
+        let old = ReputationScores::<T>::mutate(&target, |x| {
+            let old = x[0].clone();
+            x[0].score = *ir;
+            old
+        });
+        if old.nonce == nonce {
+            Self::record_score(nonce, target, Some(old.score), *ir);
+        }
     }
 
     fn set_step(step: &TIRStep) {
-        <SystemInfo<T>>::mutate(|operation_status| operation_status.step = *step);
+        let (nonce, from) = <SystemInfo<T>>::mutate(|operation_status| {
+            let from = operation_status.step;
+            operation_status.step = *step;
+            (operation_status.nonce, from)
+        });
+        if from != *step {
+            Self::deposit_event(Event::StepChanged(nonce, from, *step));
+        }
     }
 
     fn is_step(step: &TIRStep) -> bool {
@@ -226,7 +976,7 @@ impl<T: Config> Reputation<T::AccountId, T::BlockNumber, TIRStep> for Pallet<T>
     #[transactional]
     fn new_round() -> DispatchResult {
         let now_block_number = Self::now();
-        <SystemInfo<T>>::try_mutate(|operation_status| {
+        let nonce = <SystemInfo<T>>::try_mutate(|operation_status| -> Result<u32, DispatchError> {
             ensure!(
                 operation_status.step == TIRStep::Free,
                 Error::<T>::AlreadyInUpdating
@@ -235,13 +985,24 @@ impl<T: Config> Reputation<T::AccountId, T::BlockNumber, TIRStep> for Pallet<T>
                 now_block_number >= operation_status.next,
                 Error::<T>::IntervalIsTooShort
             );
+            ensure!(Self::notifying().is_none(), Error::<T>::StillNotifying);
             let next = now_block_number + operation_status.period;
             operation_status.nonce += 1;
             operation_status.next = next;
             operation_status.last = now_block_number;
             operation_status.step = TIRStep::Seed;
-            Ok(())
-        })
+            Ok(operation_status.nonce)
+        })?;
+        Superseded::<T>::remove_prefix(nonce.saturating_sub(1));
+        if Self::pins(nonce.saturating_sub(2)) == 0 {
+            RoundAccounts::<T>::remove(nonce.saturating_sub(2));
+        }
+        Leaderboards::<T>::remove(nonce.saturating_sub(2));
+        LeaderboardCuts::<T>::remove(nonce.saturating_sub(2));
+        Histograms::<T>::remove(nonce.saturating_sub(2));
+        Self::deposit_event(Event::RoundStarted(nonce));
+        Self::deposit_event(Event::StepChanged(nonce, TIRStep::Free, TIRStep::Seed));
+        Ok(())
     }
 
     fn get_reputation_new(target: &T::AccountId) -> Option<u32> {
@@ -256,38 +1017,67 @@ impl<T: Config> Reputation<T::AccountId, T::BlockNumber, TIRStep> for Pallet<T>
         }
     }
 
-    fn get_reputation(target: &T::AccountId) -> Option<u32> {
-        let system_info = Self::system_info();
-        let nonce = system_info.nonce;
-        let irs = Self::get_ir(target);
-        match system_info.step == TIRStep::Free {
-            true => {
-                if irs[0].nonce == nonce {
-                    return Some(irs[0].score);
-                }
-            }
-            false => {
-                // nonce cannot be smaller than 1
-                if irs[0].nonce == nonce - 1 {
-                    return Some(irs[0].score);
-                } else if irs[1].nonce == nonce - 1 {
-                    return Some(irs[1].score);
-                }
-            }
+    fn get_reputation(target: &T::AccountId) -> Option<ReputationValue> {
+        Self::computed_reputation(target)
+            .map(ReputationValue::Computed)
+            .or_else(|| Self::provisional_reputation(target).map(ReputationValue::Provisional))
+    }
+
+    fn get_finalized_nonce() -> u32 {
+        Self::finalized_nonce()
+    }
+
+    fn get_reputation_at(target: &T::AccountId, nonce: &u32) -> Option<u32> {
+        match *nonce <= Self::finalized_nonce() {
+            true => Self::score_at(target, *nonce),
+            false => None,
         }
-        None
+    }
+
+    fn verify_reputation_proof(target: &T::AccountId, proof: &ReputationProof) -> bool {
+        Self::reputation_root(proof.nonce).map_or(false, |root| {
+            verify_proof(&root, &leaf_hash(target, proof.score), &proof.proof)
+        })
+    }
+
+    fn pin_round(nonce: u32) {
+        Pins::<T>::mutate(nonce, |pins| *pins = pins.saturating_add(1));
+    }
+
+    fn unpin_round(nonce: u32) -> u32 {
+        let pins = Pins::<T>::mutate_exists(nonce, |maybe_pins| {
+            let pins = maybe_pins.unwrap_or_default().saturating_sub(1);
+            *maybe_pins = Some(pins).filter(|pins| *pins > 0);
+            pins
+        });
+        // Rounds before the previous one are only kept while pinned.
+        if pins > 0 || nonce.saturating_add(2) > Self::system_info().nonce {
+            return 0;
+        }
+        PinnedScores::<T>::remove_prefix(nonce);
+        RoundAccounts::<T>::take(nonce).len() as u32
     }
 
     #[transactional]
     fn refresh_reputation(user_score: &(T::AccountId, u32)) -> DispatchResult {
         let who = &user_score.0;
         let nonce = Self::system_info().nonce;
+        ensure!(
+            RoundAccounts::<T>::decode_len(nonce).unwrap_or_default() < MAX_ROUND_ACCOUNTS,
+            Error::<T>::TooManyAccounts
+        );
         ReputationScores::<T>::try_mutate(&who, |reputation| -> DispatchResult {
             ensure!(
                 reputation[0].nonce < nonce,
                 Error::<T>::ReputationAlreadyUpdated
             );
-            let old = reputation[0].clone();
+            let [old, superseded] = reputation.clone();
+            if superseded != ReputationScore::default() {
+                if Self::pins(superseded.nonce) > 0 {
+                    PinnedScores::<T>::insert(superseded.nonce, who, superseded.score);
+                }
+                Superseded::<T>::insert(nonce, who, superseded);
+            }
             *reputation = [
                 ReputationScore {
                     nonce,
@@ -296,7 +1086,10 @@ impl<T: Config> Reputation<T::AccountId, T::BlockNumber, TIRStep> for Pallet<T>
                 old,
             ];
             Ok(())
-        })
+        })?;
+        RoundAccounts::<T>::append(nonce, who);
+        Self::record_score(nonce, who, None, user_score.1);
+        Ok(())
     }
 
     fn get_last_refresh_at() -> T::BlockNumber {
@@ -318,5 +1111,12 @@ impl<T: Config> Reputation<T::AccountId, T::BlockNumber, TIRStep> for Pallet<T>
             operation_status.last = now;
             operation_status.step = TIRStep::Free;
         });
+        let nonce = operation_status.nonce;
+        Finalizing::<T>::put(nonce);
+        if !Self::thresholds().is_empty() {
+            Notifying::<T>::put((nonce, 0));
+        }
+        Self::deposit_event(Event::StepChanged(nonce, operation_status.step, TIRStep::Free));
+        Self::deposit_event(Event::RoundFinalized(nonce, now));
     }
 }
@@ -21,12 +21,16 @@ use frame_support::sp_runtime::{
 };
 use frame_support::{construct_runtime, parameter_types, traits::GenesisBuild};
 use frame_system as system;
+use frame_system::EnsureRoot;
 use sp_core::H256;
 
 pub type AccountId = u64;
+pub type Balance = u128;
 pub type BlockNumber = u64;
 
 pub const ALICE: AccountId = 1;
+pub const BOB: AccountId = 2;
+pub const CHARLIE: AccountId = 3;
 
 pub const INIT_PERIOD: BlockNumber = 10;
 
@@ -42,6 +46,7 @@ construct_runtime!(
         UncheckedExtrinsic = UncheckedExtrinsic,
     {
         System: frame_system::{Module, Call, Config, Storage, Event<T>},
+        Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
         ZdReputation: zd_reputation::{Module, Call, Storage, Event<T>},
     }
 );
@@ -49,6 +54,10 @@ construct_runtime!(
 parameter_types! {
     pub const BlockHashCount: u64 = 250;
     pub const SS58Prefix: u8 = 42;
+    pub const ExistentialDeposit: u128 = 1;
+    pub const MaxLocks: u32 = 50;
+    pub const WatchDeposit: Balance = 100;
+    pub const MaxWatches: u32 = 2;
 }
 
 parameter_types! {
@@ -58,6 +67,22 @@ parameter_types! {
 
 impl zd_reputation::Config for Test {
     type Event = Event;
+    type UpdateOrigin = EnsureRoot<AccountId>;
+    type OnRoundAborted = ();
+    type OnThresholdCrossed = ();
+    type Currency = Balances;
+    type WatchDeposit = WatchDeposit;
+    type MaxWatches = MaxWatches;
+}
+
+impl pallet_balances::Config for Test {
+    type MaxLocks = MaxLocks;
+    type Balance = Balance;
+    type Event = Event;
+    type DustRemoval = ();
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+    type WeightInfo = pallet_balances::weights::SubstrateWeight<Test>;
 }
 
 impl system::Config for Test {
@@ -82,7 +107,7 @@ impl system::Config for Test {
     type OnKilledAccount = ();
     type SystemWeightInfo = ();
     type SS58Prefix = SS58Prefix;
-    type AccountData = ();
+    type AccountData = pallet_balances::AccountData<Balance>;
 }
 
 pub struct ExtBuilder {
@@ -101,6 +126,12 @@ impl ExtBuilder {
             .build_storage::<Test>()
             .unwrap();
 
+        pallet_balances::GenesisConfig::<Test> {
+            balances: vec![(ALICE, 1000), (BOB, 1000), (CHARLIE, 1000)],
+        }
+        .assimilate_storage(&mut t)
+        .unwrap();
+
         zd_reputation::GenesisConfig::<Test> {
             period: self.period,
         }
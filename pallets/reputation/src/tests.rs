@@ -15,8 +15,12 @@
 #![cfg(test)]
 
 use super::*;
-use crate::mock::*;
-use frame_support::{assert_noop, assert_ok, dispatch};
+use crate::mock::{Event, *};
+use frame_support::{
+    assert_noop, assert_ok, dispatch,
+    traits::{Currency, OnInitialize, ReservableCurrency},
+};
+use zd_primitives::merkle::verify_proof;
 
 fn new_test_ext() -> sp_io::TestExternalities {
     let mut ext = ExtBuilder::default().build();
@@ -173,3 +177,513 @@ fn set_free_should_work() {
     });
 }
 
+#[test]
+fn round_events_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(ZdReputation::new_round());
+        let started_event = Event::zd_reputation(crate::Event::RoundStarted(1));
+        assert!(System::events().iter().any(|record| record.event == started_event));
+
+        ZdReputation::set_step(&TIRStep::Reputation);
+        let step_event =
+            Event::zd_reputation(crate::Event::StepChanged(1, TIRStep::Seed, TIRStep::Reputation));
+        assert!(System::events().iter().any(|record| record.event == step_event));
+
+        System::set_block_number(150);
+        ZdReputation::set_free();
+        let finalized_event = Event::zd_reputation(crate::Event::RoundFinalized(1, 150));
+        assert!(System::events().iter().any(|record| record.event == finalized_event));
+        let free_event =
+            Event::zd_reputation(crate::Event::StepChanged(1, TIRStep::Reputation, TIRStep::Free));
+        assert!(System::events().iter().any(|record| record.event == free_event));
+    });
+}
+
+#[test]
+fn set_same_step_should_not_emit() {
+    new_test_ext().execute_with(|| {
+        ZdReputation::set_step(&TIRStep::Free);
+        ZdReputation::set_free();
+        assert!(System::events().is_empty());
+    });
+}
+
+#[test]
+fn reputation_root_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(ZdReputation::new_round());
+        assert_ok!(ZdReputation::refresh_reputation(&(ALICE, 18)));
+        assert_ok!(ZdReputation::refresh_reputation(&(BOB, 7)));
+        ZdReputation::mutate_reputation(&BOB, &9);
+        assert_eq!(ZdReputation::reputation_root(1), None);
+        ZdReputation::set_free();
+        // Stored at the start of the next block.
+        assert_eq!(ZdReputation::reputation_root(1), None);
+        ZdReputation::on_initialize(2);
+
+        let root = merkle_root(&[leaf_hash(&ALICE, 18), leaf_hash(&BOB, 9)]);
+        assert_eq!(ZdReputation::reputation_root(1), Some(root));
+        let root_event = Event::zd_reputation(crate::Event::ReputationRootStored(1, root));
+        assert!(System::events().iter().any(|record| record.event == root_event));
+
+        let proof = ZdReputation::reputation_proof(&BOB).unwrap();
+        assert_eq!(proof.nonce, 1);
+        assert_eq!(proof.score, 9);
+        assert!(verify_proof(&root, &leaf_hash(&BOB, 9), &proof.proof));
+        assert!(!verify_proof(&root, &leaf_hash(&BOB, 7), &proof.proof));
+        assert_eq!(ZdReputation::reputation_proof(&CHARLIE), None);
+
+        // The finalised round stays provable while the next one is refreshing.
+        System::set_block_number(INIT_PERIOD + 1);
+        assert_ok!(ZdReputation::new_round());
+        assert_ok!(ZdReputation::refresh_reputation(&(ALICE, 30)));
+        let proof = ZdReputation::reputation_proof(&ALICE).unwrap();
+        assert_eq!(proof.score, 18);
+        assert!(verify_proof(&root, &leaf_hash(&ALICE, 18), &proof.proof));
+    });
+}
+
+#[test]
+fn round_accounts_should_be_bounded() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(ZdReputation::new_round());
+        <RoundAccounts<Test>>::insert(1, vec![ALICE; MAX_ROUND_ACCOUNTS - 1]);
+        assert_ok!(ZdReputation::refresh_reputation(&(BOB, 7)));
+        assert_noop!(
+            ZdReputation::refresh_reputation(&(CHARLIE, 7)),
+            Error::<Test>::TooManyAccounts
+        );
+    });
+}
+
+#[test]
+fn reputation_at_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(ZdReputation::new_round());
+        assert_ok!(ZdReputation::refresh_reputation(&(ALICE, 18)));
+        assert_eq!(ZdReputation::get_finalized_nonce(), 0);
+        assert_eq!(ZdReputation::get_reputation_at(&ALICE, &1), None);
+        ZdReputation::set_free();
+        ZdReputation::on_initialize(2);
+        assert_eq!(ZdReputation::get_finalized_nonce(), 1);
+        assert_eq!(ZdReputation::get_reputation_at(&ALICE, &1), Some(18));
+
+        let proof = ZdReputation::reputation_proof(&ALICE).unwrap();
+        assert!(ZdReputation::verify_reputation_proof(&ALICE, &proof));
+        assert!(!ZdReputation::verify_reputation_proof(&BOB, &proof));
+    });
+}
+
+#[test]
+fn leaderboard_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(ZdReputation::new_round());
+        assert_ok!(ZdReputation::refresh_reputation(&(ALICE, 18)));
+        assert_ok!(ZdReputation::refresh_reputation(&(BOB, 250)));
+        assert_ok!(ZdReputation::refresh_reputation(&(CHARLIE, 120)));
+        assert_eq!(
+            ZdReputation::leaderboard(1),
+            vec![(BOB, 250), (CHARLIE, 120), (ALICE, 18)]
+        );
+
+        // Corrections move accounts on the board and in the histogram.
+        ZdReputation::mutate_reputation(&ALICE, &300);
+        assert_eq!(
+            ZdReputation::leaderboard(1),
+            vec![(ALICE, 300), (BOB, 250), (CHARLIE, 120)]
+        );
+        let histogram = ZdReputation::histogram(1);
+        assert_eq!(histogram.len(), SCORE_BUCKETS);
+        assert_eq!((histogram[0], histogram[1], histogram[2], histogram[3]), (0, 1, 1, 1));
+
+        // Percentiles are only available once the round is finalised.
+        assert_eq!(ZdReputation::percentile(&ALICE), None);
+        ZdReputation::set_free();
+        assert_eq!(
+            ZdReputation::percentile(&ALICE),
+            Some(Permill::from_rational_approximation(5u32, 6u32))
+        );
+        assert_eq!(
+            ZdReputation::percentile(&CHARLIE),
+            Some(Permill::from_rational_approximation(1u32, 6u32))
+        );
+
+        // The board of a finalised round is frozen.
+        System::set_block_number(INIT_PERIOD + 1);
+        assert_ok!(ZdReputation::new_round());
+        assert_ok!(ZdReputation::refresh_reputation(&(CHARLIE, 900)));
+        assert_eq!(ZdReputation::leaderboard(1)[0], (ALICE, 300));
+        assert_eq!(ZdReputation::leaderboard(2), vec![(CHARLIE, 900)]);
+    });
+}
+
+#[test]
+fn leaderboard_should_be_bounded() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(ZdReputation::new_round());
+        let size = LEADERBOARD_SIZE as u64;
+        for who in 1..=size + 10 {
+            assert_ok!(ZdReputation::refresh_reputation(&(who, who as u32)));
+        }
+        let board = ZdReputation::leaderboard(1);
+        assert_eq!(board.len(), LEADERBOARD_SIZE);
+        assert_eq!(board[0], (size + 10, (size + 10) as u32));
+        assert_eq!(board[LEADERBOARD_SIZE - 1], (11, 11));
+
+        assert_eq!(ZdReputation::leaderboard_cut(1), Some(10));
+
+        // A listed account lowered below those left off is replaced by the
+        // highest of them.
+        ZdReputation::mutate_reputation(&11, &1);
+        let board = ZdReputation::leaderboard(1);
+        assert_eq!(board.len(), LEADERBOARD_SIZE);
+        assert_eq!(board[LEADERBOARD_SIZE - 1], (10, 10));
+        assert!(!board.iter().any(|(who, _)| *who == 11));
+        assert_eq!(ZdReputation::leaderboard_cut(1), Some(9));
+
+        // Accounts below the tail are left off.
+        assert_ok!(ZdReputation::refresh_reputation(&(size + 11, 5)));
+        assert_eq!(ZdReputation::leaderboard(1).len(), LEADERBOARD_SIZE);
+        assert_eq!(ZdReputation::leaderboard_cut(1), Some(9));
+        ZdReputation::mutate_reputation(&4, &11);
+        let board = ZdReputation::leaderboard(1);
+        assert_eq!(board.len(), LEADERBOARD_SIZE);
+        assert_eq!(board[LEADERBOARD_SIZE - 1], (4, 11));
+
+        // An unlisted account raised above the tail joins it.
+        ZdReputation::mutate_reputation(&3, &500);
+        let board = ZdReputation::leaderboard(1);
+        assert_eq!(board[0], (3, 500));
+        assert_eq!(board.len(), LEADERBOARD_SIZE);
+    });
+}
+
+#[test]
+fn abort_round_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(ZdReputation::new_round());
+        assert_ok!(ZdReputation::refresh_reputation(&(ALICE, 18)));
+        ZdReputation::set_free();
+
+        System::set_block_number(INIT_PERIOD + 1);
+        assert_ok!(ZdReputation::new_round());
+        ZdReputation::set_step(&TIRStep::Reputation);
+        assert_ok!(ZdReputation::refresh_reputation(&(ALICE, 30)));
+        assert_ok!(ZdReputation::refresh_reputation(&(BOB, 7)));
+        assert_eq!(ZdReputation::get_reputation_new(&ALICE), Some(30));
+
+        assert_ok!(ZdReputation::abort_round(Origin::root()));
+
+        assert_eq!(ZdReputation::system_info().nonce, 1);
+        assert!(ZdReputation::is_step(&TIRStep::Free));
+        assert_eq!(
+            ZdReputation::get_reputation(&ALICE),
+            Some(ReputationValue::Computed(18))
+        );
+        assert_eq!(ZdReputation::get_reputation(&BOB), None);
+        assert_eq!(ZdReputation::round_accounts(2), Vec::<AccountId>::new());
+        assert!(ZdReputation::leaderboard(2).is_empty());
+        assert_eq!(ZdReputation::leaderboard(1), vec![(ALICE, 18)]);
+
+        let step_event = Event::zd_reputation(crate::Event::StepChanged(
+            2,
+            TIRStep::Reputation,
+            TIRStep::Free,
+        ));
+        let aborted_event = Event::zd_reputation(crate::Event::RoundAborted(2));
+        assert!(System::events().iter().any(|record| record.event == step_event));
+        assert!(System::events().iter().any(|record| record.event == aborted_event));
+
+        // The round can be refreshed again.
+        assert_ok!(ZdReputation::new_round());
+        assert_ok!(ZdReputation::refresh_reputation(&(ALICE, 40)));
+    });
+}
+
+#[test]
+fn abort_round_should_restore_older_score() {
+    new_test_ext().execute_with(|| {
+        for (round, score) in [(1u64, 18u32), (2, 25)].iter() {
+            System::set_block_number(INIT_PERIOD * round + 1);
+            assert_ok!(ZdReputation::new_round());
+            ZdReputation::set_step(&TIRStep::Reputation);
+            assert_ok!(ZdReputation::refresh_reputation(&(ALICE, *score)));
+            ZdReputation::set_free();
+        }
+
+        System::set_block_number(INIT_PERIOD * 3 + 1);
+        assert_ok!(ZdReputation::new_round());
+        ZdReputation::set_step(&TIRStep::Reputation);
+        assert_ok!(ZdReputation::refresh_reputation(&(ALICE, 30)));
+        assert_eq!(
+            ZdReputation::superseded(3, ALICE),
+            Some(ReputationScore { nonce: 1, score: 18 })
+        );
+
+        assert_ok!(ZdReputation::abort_round(Origin::root()));
+        assert_eq!(
+            ZdReputation::get_ir(ALICE),
+            [
+                ReputationScore { nonce: 2, score: 25 },
+                ReputationScore { nonce: 1, score: 18 },
+            ]
+        );
+        assert_eq!(ZdReputation::superseded(3, ALICE), None);
+    });
+}
+
+#[test]
+fn abort_round_should_fail() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            ZdReputation::abort_round(Origin::root()),
+            Error::<Test>::NotInRound
+        );
+        assert_ok!(ZdReputation::new_round());
+        assert_noop!(
+            ZdReputation::abort_round(Origin::signed(ALICE)),
+            dispatch::DispatchError::BadOrigin
+        );
+    });
+}
+
+fn crossed(who: AccountId, threshold: u32, old: u32, new: u32) -> bool {
+    let event = Event::zd_reputation(crate::Event::ThresholdCrossed(who, threshold, old, new));
+    System::events().iter().any(|record| record.event == event)
+}
+
+#[test]
+fn watch_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(ZdReputation::watch(Origin::signed(ALICE), 20));
+        assert_ok!(ZdReputation::watch(Origin::signed(BOB), 20));
+        assert_ok!(ZdReputation::watch(Origin::signed(BOB), 10));
+        assert_eq!(ZdReputation::thresholds(), vec![(10, 1), (20, 2)]);
+        assert_eq!(ZdReputation::watching(BOB, 10), Some(100));
+        assert_eq!(ZdReputation::watch_count(BOB), 2);
+        assert_eq!(Balances::reserved_balance(BOB), 200);
+        assert_noop!(
+            ZdReputation::watch(Origin::signed(ALICE), 20),
+            Error::<Test>::AlreadyWatching
+        );
+        assert_noop!(
+            ZdReputation::watch(Origin::signed(ALICE), 0),
+            Error::<Test>::ZeroThreshold
+        );
+        assert_noop!(
+            ZdReputation::watch(Origin::signed(BOB), 30),
+            Error::<Test>::TooManyWatches
+        );
+        assert_noop!(
+            ZdReputation::watch(Origin::signed(4), 30),
+            pallet_balances::Error::<Test, _>::InsufficientBalance
+        );
+        assert_noop!(
+            ZdReputation::watch(Origin::root(), 30),
+            dispatch::DispatchError::BadOrigin
+        );
+
+        assert_ok!(ZdReputation::unwatch(Origin::signed(BOB), 20));
+        assert_ok!(ZdReputation::unwatch(Origin::signed(BOB), 10));
+        assert_eq!(ZdReputation::thresholds(), vec![(20, 1)]);
+        assert_eq!(ZdReputation::watch_count(BOB), 0);
+        assert_eq!(Balances::reserved_balance(BOB), 0);
+        assert_noop!(
+            ZdReputation::unwatch(Origin::signed(BOB), 10),
+            Error::<Test>::NotWatching
+        );
+
+        // Pallets can watch thresholds for accounts without a deposit.
+        assert_ok!(ZdReputation::do_watch(&CHARLIE, 30, 0));
+        assert_eq!(ZdReputation::watch_count(CHARLIE), 1);
+        assert_eq!(Balances::reserved_balance(CHARLIE), 0);
+        assert_ok!(ZdReputation::unwatch(Origin::signed(CHARLIE), 30));
+        assert_eq!(Balances::free_balance(CHARLIE), 1000);
+
+        for threshold in 1..MAX_THRESHOLDS as u32 {
+            assert_ok!(ZdReputation::add_threshold(threshold + 100));
+        }
+        assert_noop!(
+            ZdReputation::watch(Origin::signed(BOB), 10),
+            Error::<Test>::TooManyThresholds
+        );
+    });
+}
+
+#[test]
+fn threshold_crossing_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(ZdReputation::watch(Origin::signed(ALICE), 10));
+        assert_ok!(ZdReputation::watch(Origin::signed(ALICE), 20));
+
+        assert_ok!(ZdReputation::new_round());
+        assert_ok!(ZdReputation::refresh_reputation(&(ALICE, 18)));
+        assert_ok!(ZdReputation::refresh_reputation(&(BOB, 5)));
+        // Unfinalised values are not reported.
+        assert!(!crossed(ALICE, 10, 0, 18));
+        ZdReputation::set_free();
+        assert!(!crossed(ALICE, 10, 0, 18));
+        ZdReputation::on_initialize(2);
+        assert_eq!(ZdReputation::notifying(), None);
+        assert!(crossed(ALICE, 10, 0, 18));
+        assert!(!crossed(ALICE, 20, 0, 18));
+
+        System::set_block_number(INIT_PERIOD + 1);
+        assert_ok!(ZdReputation::new_round());
+        assert_ok!(ZdReputation::refresh_reputation(&(ALICE, 25)));
+        ZdReputation::mutate_reputation(&ALICE, &9);
+        ZdReputation::set_free();
+        // No round starts before the crossings are reported.
+        System::set_block_number(INIT_PERIOD * 2 + 1);
+        assert_noop!(ZdReputation::new_round(), Error::<Test>::StillNotifying);
+        ZdReputation::on_initialize(INIT_PERIOD * 2 + 1);
+        assert!(crossed(ALICE, 10, 18, 9));
+        assert!(!crossed(ALICE, 20, 18, 25));
+
+        // Accounts that are not refreshed lose their reputation.
+        assert_ok!(ZdReputation::new_round());
+        assert_ok!(ZdReputation::refresh_reputation(&(BOB, 30)));
+        ZdReputation::set_free();
+        ZdReputation::on_initialize(INIT_PERIOD * 2 + 2);
+        assert!(crossed(BOB, 10, 0, 30));
+        assert!(crossed(BOB, 20, 0, 30));
+        assert!(!crossed(ALICE, 10, 9, 0));
+
+        System::set_block_number(INIT_PERIOD * 3 + 1);
+        assert_ok!(ZdReputation::new_round());
+        assert_ok!(ZdReputation::refresh_reputation(&(ALICE, 12)));
+        ZdReputation::set_free();
+        ZdReputation::on_initialize(INIT_PERIOD * 3 + 2);
+        assert!(crossed(BOB, 10, 30, 0));
+        assert!(crossed(BOB, 20, 30, 0));
+        assert!(crossed(ALICE, 10, 0, 12));
+    });
+}
+
+#[test]
+fn threshold_crossings_should_be_batched() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(ZdReputation::watch(Origin::signed(ALICE), 10));
+        assert_ok!(ZdReputation::new_round());
+        let size = NOTIFY_BATCH as AccountId + 10;
+        for who in 1..=size {
+            assert_ok!(ZdReputation::refresh_reputation(&(who, 20)));
+        }
+        ZdReputation::set_free();
+
+        ZdReputation::on_initialize(2);
+        assert_eq!(ZdReputation::notifying(), Some((1, 1)));
+        assert!(!NotifyQueue::<Test>::contains_key(0));
+        assert_eq!(ZdReputation::notify_queue(1).len(), 10);
+        assert!(crossed(NOTIFY_BATCH as AccountId, 10, 0, 20));
+        assert!(!crossed(size, 10, 0, 20));
+
+        ZdReputation::on_initialize(3);
+        assert_eq!(ZdReputation::notifying(), None);
+        assert!(!NotifyQueue::<Test>::contains_key(1));
+        assert!(crossed(size, 10, 0, 20));
+    });
+}
+
+#[test]
+fn vouch_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(ZdReputation::new_round());
+        assert_ok!(ZdReputation::refresh_reputation(&(ALICE, 1000)));
+        assert_ok!(ZdReputation::refresh_reputation(&(BOB, 500)));
+        ZdReputation::set_free();
+
+        assert_ok!(ZdReputation::vouch(Origin::signed(ALICE), CHARLIE, 60));
+        assert_ok!(ZdReputation::vouch(Origin::signed(BOB), CHARLIE, 60));
+        assert_eq!(
+            ZdReputation::get_reputation(&CHARLIE),
+            Some(ReputationValue::Provisional(MAX_PROVISIONAL_SCORE))
+        );
+        // Only the part below the cap is spent, and taken off the voucher.
+        assert_eq!(ZdReputation::vouched(BOB), (1, 40));
+        assert_eq!(
+            ZdReputation::get_reputation(&ALICE),
+            Some(ReputationValue::Computed(940))
+        );
+        assert_eq!(
+            ZdReputation::get_reputation(&BOB),
+            Some(ReputationValue::Computed(460))
+        );
+        assert_eq!(ZdReputation::score_at(&BOB, 1), Some(500));
+        let vouched_event = Event::zd_reputation(crate::Event::Vouched(BOB, CHARLIE, 40));
+        assert!(System::events().iter().any(|record| record.event == vouched_event));
+
+        // Provisional reputation lasts while the next round is refreshed.
+        System::set_block_number(INIT_PERIOD + 1);
+        assert_ok!(ZdReputation::new_round());
+        assert_ok!(ZdReputation::refresh_reputation(&(ALICE, 1000)));
+        assert_ok!(ZdReputation::refresh_reputation(&(CHARLIE, 20)));
+        assert_eq!(
+            ZdReputation::get_reputation(&CHARLIE),
+            Some(ReputationValue::Provisional(MAX_PROVISIONAL_SCORE))
+        );
+
+        // And is replaced by the computed one, the voucher gets its reputation back.
+        ZdReputation::set_free();
+        assert_eq!(
+            ZdReputation::get_reputation(&CHARLIE),
+            Some(ReputationValue::Computed(20))
+        );
+        assert_eq!(
+            ZdReputation::get_reputation(&ALICE),
+            Some(ReputationValue::Computed(1000))
+        );
+    });
+}
+
+#[test]
+fn provisional_reputation_should_expire() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(ZdReputation::new_round());
+        assert_ok!(ZdReputation::refresh_reputation(&(ALICE, 1000)));
+        ZdReputation::set_free();
+        assert_ok!(ZdReputation::vouch(Origin::signed(ALICE), CHARLIE, 30));
+
+        System::set_block_number(INIT_PERIOD + 1);
+        assert_ok!(ZdReputation::new_round());
+        assert_ok!(ZdReputation::refresh_reputation(&(ALICE, 1000)));
+        ZdReputation::set_free();
+        assert_eq!(ZdReputation::get_reputation(&CHARLIE), None);
+
+        // The vouching limit is reset every round.
+        assert_ok!(ZdReputation::vouch(Origin::signed(ALICE), CHARLIE, 100));
+        assert_eq!(ZdReputation::vouched(ALICE), (2, 100));
+    });
+}
+
+#[test]
+fn vouch_should_fail() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(ZdReputation::new_round());
+        assert_ok!(ZdReputation::refresh_reputation(&(ALICE, 300)));
+        assert_ok!(ZdReputation::refresh_reputation(&(BOB, 10)));
+        ZdReputation::set_free();
+
+        assert_noop!(
+            ZdReputation::vouch(Origin::signed(ALICE), ALICE, 10),
+            Error::<Test>::SelfVouch
+        );
+        assert_noop!(
+            ZdReputation::vouch(Origin::signed(CHARLIE), ALICE, 10),
+            Error::<Test>::NoReputation
+        );
+        assert_noop!(
+            ZdReputation::vouch(Origin::signed(ALICE), BOB, 10),
+            Error::<Test>::AlreadyReputable
+        );
+        assert_noop!(
+            ZdReputation::vouch(Origin::signed(ALICE), CHARLIE, 31),
+            Error::<Test>::VouchLimitExceeded
+        );
+        assert_ok!(ZdReputation::vouch(Origin::signed(ALICE), CHARLIE, 30));
+        assert_noop!(
+            ZdReputation::vouch(Origin::signed(ALICE), CHARLIE, 1),
+            Error::<Test>::VouchLimitExceeded
+        );
+    });
+}
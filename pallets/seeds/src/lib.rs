@@ -28,15 +28,51 @@
 //!
 //! ### Dispatchable Functions
 //!
-//! - `new_seed` - Add new seeds, root access required.
-//! - `remove_seed` - Remove seeds, root access required.
+//! - `new_seed` - Add new seeds, requires `UpdateOrigin`.
+//! - `remove_seed` - Remove seeds, requires `UpdateOrigin`.
+//! - `set_seeds` - Replace all seeds, requires `UpdateOrigin`.
+//! - `seed_heartbeat` - Signal that a seed is alive in the current round.
+//!
+//! ### Seed history
+//!
+//! Each seed has a `SeedInfo` record of the round it was elected for, its
+//! centrality score, its pathfinder and whether it was set by governance or
+//! computed. The seeds used in each round are archived in `SeedArchive`, so that
+//! the paths of old rounds can still be verified after the seeds have changed.
+//!
+//! ### Term limits
+//!
+//! With a non-zero `MaxSeedTerms`, an account that has been a seed for that many
+//! consecutive rounds cannot be a seed candidate again for the next
+//! `SeedCooldown` rounds, at least one. When the current seeds are kept for a
+//! round instead of being elected, those that reached the limit are dropped.
+//!
+//! ### Liveness
+//!
+//! Seeds are expected to send a `seed_heartbeat` in each round they serve in.
+//! With a non-zero `MaxMissedHeartbeats`, seeds that missed that many rounds in a
+//! row are dropped when the next round starts. The first round of a term is not
+//! counted, and heartbeats sent while the system is free count for the last
+//! finalised round. If the round is aborted, the check is undone along with
+//! the seeds it dropped.
+//!
+//! ### Genesis Config
+//!
+//! The initial seeds are set in `GenesisConfig`, at most `MaxSeeds` of them.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
 
+use frame_support::{
+    codec::{Decode, Encode},
+    ensure,
+    traits::Get,
+};
 use orml_utilities::OrderedSet;
+use sp_runtime::{DispatchError, DispatchResult, RuntimeDebug};
+use sp_std::vec::Vec;
 pub use pallet::*;
-use zd_primitives::TIRStep;
+use zd_primitives::{SeedInfo, SeedOrigin, TIRStep};
 use zd_support::{Reputation, SeedsBase};
 
 #[cfg(test)]
@@ -44,17 +80,53 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+/// Consecutive rounds an account has been a seed for.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct SeedTerm {
+    /// The first round of the term.
+    pub first: u32,
+    /// The last round of the term.
+    pub last: u32,
+}
+
+impl SeedTerm {
+    /// Number of rounds in the term.
+    pub fn rounds(&self) -> u32 {
+        self.last.saturating_sub(self.first).saturating_add(1)
+    }
+}
+
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
     use frame_support::{dispatch::DispatchResultWithPostInfo, pallet_prelude::*};
-    use frame_system::ensure_root;
-    use frame_system::pallet_prelude::*;
+    use frame_system::{ensure_signed, pallet_prelude::*};
 
     #[pallet::config]
     pub trait Config: frame_system::Config {
         type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
         type Reputation: Reputation<Self::AccountId, Self::BlockNumber, TIRStep>;
+
+        /// The origin which may add or remove seeds.
+        type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+        /// Maximum number of seeds.
+        #[pallet::constant]
+        type MaxSeeds: Get<u32>;
+
+        /// Maximum number of consecutive rounds as a seed, zero for no limit.
+        #[pallet::constant]
+        type MaxSeedTerms: Get<u32>;
+
+        /// Number of rounds an account that reached `MaxSeedTerms` cannot be a
+        /// seed candidate for.
+        #[pallet::constant]
+        type SeedCooldown: Get<u32>;
+
+        /// Number of heartbeats in a row a seed may miss before it is dropped,
+        /// zero to never drop seeds.
+        #[pallet::constant]
+        type MaxMissedHeartbeats: Get<u32>;
     }
 
     #[pallet::pallet]
@@ -65,6 +137,62 @@ pub mod pallet {
     #[pallet::getter(fn get_seeds)]
     pub type Seeds<T: Config> = StorageValue<_, OrderedSet<T::AccountId>, ValueQuery>;
 
+    /// Metadata of the current seeds.
+    #[pallet::storage]
+    #[pallet::getter(fn seed_info)]
+    pub type SeedInfos<T: Config> =
+        StorageMap<_, Twox64Concat, T::AccountId, SeedInfo<T::AccountId>, OptionQuery>;
+
+    /// The seeds used in round `nonce`.
+    #[pallet::storage]
+    #[pallet::getter(fn seeds_at)]
+    pub type SeedArchive<T: Config> =
+        StorageMap<_, Twox64Concat, u32, Vec<T::AccountId>, ValueQuery>;
+
+    /// The latest term of each account that has been a seed.
+    #[pallet::storage]
+    #[pallet::getter(fn seed_term)]
+    pub type SeedTerms<T: Config> =
+        StorageMap<_, Twox64Concat, T::AccountId, SeedTerm, OptionQuery>;
+
+    /// The latest round each seed sent a heartbeat in.
+    #[pallet::storage]
+    #[pallet::getter(fn last_heartbeat)]
+    pub type LastHeartbeats<T: Config> =
+        StorageMap<_, Twox64Concat, T::AccountId, u32, ValueQuery>;
+
+    /// Number of heartbeats in a row each seed has missed in its latest term.
+    #[pallet::storage]
+    #[pallet::getter(fn missed_heartbeats)]
+    pub type MissedHeartbeats<T: Config> =
+        StorageMap<_, Twox64Concat, T::AccountId, u32, ValueQuery>;
+
+    /// The latest round whose heartbeats have been checked.
+    #[pallet::storage]
+    #[pallet::getter(fn checked_round)]
+    pub type CheckedRound<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+    /// Initializes the seeds to the given accounts.
+    #[pallet::genesis_config]
+    pub struct GenesisConfig<T: Config> {
+        pub seeds: Vec<T::AccountId>,
+    }
+
+    #[cfg(feature = "std")]
+    impl<T: Config> Default for GenesisConfig<T> {
+        fn default() -> Self {
+            GenesisConfig { seeds: Vec::new() }
+        }
+    }
+
+    #[pallet::genesis_build]
+    impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+        fn build(&self) {
+            Pallet::<T>::do_set_seeds(self.seeds.clone())
+                .expect("Genesis seeds cannot exceed MaxSeeds");
+        }
+    }
+
     #[pallet::event]
     #[pallet::metadata(T::AccountId = "AccountId")]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
@@ -73,6 +201,16 @@ pub mod pallet {
         SeedAdded(T::AccountId),
         /// Seed removed. \[seed\]
         SeedRemoved(T::AccountId),
+        /// All seeds replaced. \[number\]
+        SeedsReplaced(u32),
+        /// A seed is alive in round `nonce`. \[seed, nonce\]
+        HeartbeatReceived(T::AccountId, u32),
+        /// A seed missed a heartbeat. \[seed, missed\]
+        HeartbeatMissed(T::AccountId, u32),
+        /// A seed that missed too many heartbeats was dropped. \[seed\]
+        SeedDropped(T::AccountId),
+        /// A seed that reached the term limit was dropped. \[seed\]
+        SeedRetired(T::AccountId),
     }
 
     #[pallet::error]
@@ -85,6 +223,10 @@ pub mod pallet {
         StatusErr,
         /// Calculation overflow.
         Overflow,
+        /// Maximum number of seeds reached.
+        TooManySeeds,
+        /// Heartbeat already sent in this round.
+        AlreadyHeartbeat,
     }
 
     #[pallet::hooks]
@@ -94,25 +236,25 @@ pub mod pallet {
     impl<T: Config> Pallet<T> {
         /// Add seed, or return `Err` if seeds already exist.
         ///
-        /// The dispatch origin for this call must be `Signed` by the transactor.
-        #[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
+        /// The dispatch origin for this call must be `UpdateOrigin`.
+        #[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2,2))]
         pub fn new_seed(origin: OriginFor<T>, seed: T::AccountId) -> DispatchResultWithPostInfo {
-            ensure_root(origin)?;
+            T::UpdateOrigin::ensure_origin(origin)?;
             ensure!(
                 T::Reputation::is_step(&TIRStep::Free),
                 Error::<T>::StatusErr
             );
             ensure!(!Self::is_seed(&seed), Error::<T>::AlreadySeedUser);
-            Self::add_seed(&seed);
+            Self::add_seed(&seed)?;
             Ok(().into())
         }
 
         /// Remove seed, or return `Err` if seeds not exist.
         ///
-        /// The dispatch origin for this call must be `Signed` by the transactor.
-        #[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
+        /// The dispatch origin for this call must be `UpdateOrigin`.
+        #[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,2))]
         pub fn remove_seed(origin: OriginFor<T>, seed: T::AccountId) -> DispatchResultWithPostInfo {
-            ensure_root(origin)?;
+            T::UpdateOrigin::ensure_origin(origin)?;
             ensure!(
                 T::Reputation::is_step(&TIRStep::Free),
                 Error::<T>::StatusErr
@@ -122,9 +264,144 @@ pub mod pallet {
             Seeds::<T>::mutate(|seeds| {
                 seeds.remove(&seed);
             });
+            SeedInfos::<T>::remove(&seed);
             Self::deposit_event(Event::SeedRemoved(seed));
             Ok(().into())
         }
+
+        /// Replace all seeds with `seeds`, duplicates are ignored.
+        ///
+        /// The dispatch origin for this call must be `UpdateOrigin`.
+        #[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1 + seeds.len() as u64))]
+        pub fn set_seeds(
+            origin: OriginFor<T>,
+            seeds: Vec<T::AccountId>,
+        ) -> DispatchResultWithPostInfo {
+            T::UpdateOrigin::ensure_origin(origin)?;
+            ensure!(
+                T::Reputation::is_step(&TIRStep::Free),
+                Error::<T>::StatusErr
+            );
+            let count = Self::do_set_seeds(seeds)?;
+            Self::deposit_event(Event::SeedsReplaced(count));
+            Ok(().into())
+        }
+
+        /// Signal that the calling seed is alive in the current round, or in the
+        /// last finalised one if the system is free.
+        ///
+        /// The dispatch origin for this call must be a seed.
+        #[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4,2))]
+        pub fn seed_heartbeat(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(Self::is_seed(&who), Error::<T>::NotSeedUser);
+            let nonce = Self::heartbeat_nonce();
+            ensure!(
+                Self::last_heartbeat(&who) < nonce,
+                Error::<T>::AlreadyHeartbeat
+            );
+            LastHeartbeats::<T>::insert(&who, nonce);
+            MissedHeartbeats::<T>::remove(&who);
+            Self::deposit_event(Event::HeartbeatReceived(who, nonce));
+            Ok(().into())
+        }
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// The round seeds added now are elected for: the one in progress, or the
+    /// next one if the system is free.
+    pub fn round_nonce() -> u32 {
+        T::Reputation::get_finalized_nonce().saturating_add(1)
+    }
+
+    /// The round heartbeats are sent for: the one in progress, or the last
+    /// finalised one if the system is free.
+    pub fn heartbeat_nonce() -> u32 {
+        let nonce = T::Reputation::get_finalized_nonce();
+        match T::Reputation::is_step(&TIRStep::Free) {
+            true => nonce,
+            false => nonce.saturating_add(1),
+        }
+    }
+
+    /// Whether `seed` served in round `checked`, not for the first time,
+    /// without sending a heartbeat.
+    fn missed_round(seed: &T::AccountId, checked: u32) -> bool {
+        if Self::last_heartbeat(seed) >= checked {
+            return false;
+        }
+        matches!(
+            Self::seed_term(seed),
+            Some(term) if term.first < checked && term.last >= checked
+        )
+    }
+
+    /// Returns the metadata of `seed`, seeds stored without metadata are taken
+    /// to be set by governance in round 0.
+    pub fn info_of(seed: &T::AccountId) -> SeedInfo<T::AccountId> {
+        Self::seed_info(seed).unwrap_or(SeedInfo {
+            nonce: 0,
+            score: 0,
+            pathfinder: None,
+            origin: SeedOrigin::Governance,
+        })
+    }
+
+    fn governance_info() -> SeedInfo<T::AccountId> {
+        SeedInfo {
+            nonce: Self::round_nonce(),
+            score: 0,
+            pathfinder: None,
+            origin: SeedOrigin::Governance,
+        }
+    }
+
+    pub(crate) fn do_set_seeds(seeds: Vec<T::AccountId>) -> Result<u32, DispatchError> {
+        let info = Self::governance_info();
+        Self::replace_seeds(
+            seeds
+                .into_iter()
+                .map(|seed| (seed, info.clone()))
+                .collect(),
+        )
+    }
+
+    fn replace_seeds(
+        seeds: Vec<(T::AccountId, SeedInfo<T::AccountId>)>,
+    ) -> Result<u32, DispatchError> {
+        let set = OrderedSet::from(
+            seeds
+                .iter()
+                .map(|(seed, _)| seed.clone())
+                .collect::<Vec<_>>(),
+        );
+        ensure!(
+            set.len() as u32 <= T::MaxSeeds::get(),
+            Error::<T>::TooManySeeds
+        );
+        let count = set.len() as u32;
+        Seeds::<T>::put(set);
+        SeedInfos::<T>::remove_all();
+        for (seed, info) in seeds {
+            SeedInfos::<T>::insert(seed, info);
+        }
+        Ok(count)
+    }
+
+    fn insert_seed(new_seed: &T::AccountId, info: SeedInfo<T::AccountId>) -> DispatchResult {
+        Seeds::<T>::try_mutate(|seeds| -> DispatchResult {
+            if seeds.insert(new_seed.clone()) {
+                ensure!(
+                    seeds.len() as u32 <= T::MaxSeeds::get(),
+                    Error::<T>::TooManySeeds
+                );
+            }
+            Ok(())
+        })?;
+        SeedInfos::<T>::insert(new_seed, info);
+        Self::deposit_event(Event::SeedAdded(new_seed.clone()));
+        Ok(())
     }
 }
 
@@ -133,16 +410,148 @@ impl<T: Config> SeedsBase<T::AccountId> for Pallet<T> {
         Seeds::<T>::get().len() as u32
     }
 
+    fn max_seed_count() -> u32 {
+        T::MaxSeeds::get()
+    }
+
+    fn get_seeds() -> Vec<T::AccountId> {
+        Seeds::<T>::get().0
+    }
+
     fn is_seed(seed: &T::AccountId) -> bool {
         Seeds::<T>::get().contains(seed)
     }
 
+    fn get_seed_infos() -> Vec<(T::AccountId, SeedInfo<T::AccountId>)> {
+        Seeds::<T>::get()
+            .0
+            .into_iter()
+            .map(|seed| {
+                let info = Self::info_of(&seed);
+                (seed, info)
+            })
+            .collect()
+    }
+
     fn remove_all() {
         Seeds::<T>::kill();
+        SeedInfos::<T>::remove_all();
     }
 
-    fn add_seed(new_seed: &T::AccountId) {
-        Seeds::<T>::mutate(|seeds| seeds.insert(new_seed.clone()));
-        Self::deposit_event(Event::SeedAdded(new_seed.clone()));
+    fn add_seed(new_seed: &T::AccountId) -> DispatchResult {
+        Self::insert_seed(new_seed, Self::governance_info())
+    }
+
+    fn add_computed_seed(
+        new_seed: &T::AccountId,
+        score: u64,
+        pathfinder: &T::AccountId,
+    ) -> DispatchResult {
+        Self::insert_seed(
+            new_seed,
+            SeedInfo {
+                nonce: Self::round_nonce(),
+                score,
+                pathfinder: Some(pathfinder.clone()),
+                origin: SeedOrigin::Computed,
+            },
+        )
+    }
+
+    fn restore_seeds(seeds: Vec<(T::AccountId, SeedInfo<T::AccountId>)>) -> DispatchResult {
+        Self::replace_seeds(seeds)?;
+        Ok(())
+    }
+
+    fn drop_inactive() {
+        let max_missed = T::MaxMissedHeartbeats::get();
+        let checked = Self::round_nonce().saturating_sub(1);
+        if max_missed == 0 || checked <= Self::checked_round() {
+            return;
+        }
+        CheckedRound::<T>::put(checked);
+        for seed in Seeds::<T>::get().0 {
+            // Only seeds that served in the checked round, and not for the first
+            // time, are expected to have sent a heartbeat.
+            if !Self::missed_round(&seed, checked) {
+                continue;
+            }
+            let missed = MissedHeartbeats::<T>::mutate(&seed, |missed| {
+                *missed = missed.saturating_add(1);
+                *missed
+            });
+            Self::deposit_event(Event::HeartbeatMissed(seed.clone(), missed));
+            if missed >= max_missed {
+                Seeds::<T>::mutate(|seeds| {
+                    seeds.remove(&seed);
+                });
+                SeedInfos::<T>::remove(&seed);
+                Self::deposit_event(Event::SeedDropped(seed));
+            }
+        }
+    }
+
+    fn undo_drop_inactive() {
+        let checked = Self::round_nonce().saturating_sub(1);
+        if T::MaxMissedHeartbeats::get() == 0 || Self::checked_round() != checked {
+            return;
+        }
+        CheckedRound::<T>::put(checked.saturating_sub(1));
+        // Seeds that sent a heartbeat since have no missed heartbeats to undo.
+        for seed in Seeds::<T>::get().0 {
+            if Self::missed_round(&seed, checked) {
+                MissedHeartbeats::<T>::mutate(&seed, |missed| {
+                    *missed = missed.saturating_sub(1);
+                });
+            }
+        }
+    }
+
+    fn is_eligible(who: &T::AccountId) -> bool {
+        let max_terms = T::MaxSeedTerms::get();
+        if max_terms == 0 {
+            return true;
+        }
+        match Self::seed_term(who) {
+            Some(term) if term.rounds() >= max_terms => {
+                Self::round_nonce() > term.last.saturating_add(T::SeedCooldown::get().max(1))
+            }
+            _ => true,
+        }
+    }
+
+    fn archive_seeds() {
+        let nonce = Self::round_nonce();
+        let seeds = Seeds::<T>::get().0;
+        for seed in seeds.iter() {
+            SeedTerms::<T>::mutate(seed, |maybe_term| {
+                // A round archived again after being aborted continues the term.
+                let first = match maybe_term {
+                    Some(term) if term.last.saturating_add(1) >= nonce => term.first,
+                    _ => {
+                        MissedHeartbeats::<T>::remove(seed);
+                        nonce
+                    }
+                };
+                *maybe_term = Some(SeedTerm { first, last: nonce });
+            });
+        }
+        SeedArchive::<T>::insert(nonce, seeds);
+    }
+
+    fn drop_ineligible() -> u32 {
+        let mut dropped = 0u32;
+        for seed in Seeds::<T>::get().0 {
+            if Self::is_eligible(&seed) {
+                continue;
+            }
+            Seeds::<T>::mutate(|seeds| {
+                seeds.remove(&seed);
+            });
+            SeedInfos::<T>::remove(&seed);
+            dropped = dropped.saturating_add(1);
+            Self::deposit_event(Event::SeedRetired(seed));
+        }
+        dropped
     }
 }
@@ -21,9 +21,11 @@ use frame_support::sp_runtime::{
 };
 use frame_support::{construct_runtime, parameter_types, traits::GenesisBuild};
 pub use frame_system as system;
+use frame_system::EnsureRoot;
 use sp_core::H256;
 
 pub type AccountId = u64;
+pub type Balance = u128;
 pub type BlockNumber = u64;
 
 pub const ALICE: AccountId = 1;
@@ -43,14 +45,19 @@ construct_runtime!(
         UncheckedExtrinsic = UncheckedExtrinsic,
     {
         System: frame_system::{Module, Call, Config, Storage, Event<T>},
+        Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
         ZdReputation: zd_reputation::{Module, Call, Storage, Event<T>, Config<T>},
-        ZdSeeds: zd_seeds::{Module, Call, Storage, Event<T>},
+        ZdSeeds: zd_seeds::{Module, Call, Storage, Event<T>, Config<T>},
     }
 );
 
 parameter_types! {
     pub const BlockHashCount: u64 = 250;
     pub const SS58Prefix: u8 = 42;
+    pub const ExistentialDeposit: u128 = 1;
+    pub const MaxLocks: u32 = 50;
+    pub const WatchDeposit: Balance = 100;
+    pub const MaxWatches: u32 = 2;
 }
 
 parameter_types! {
@@ -60,11 +67,39 @@ parameter_types! {
 
 impl zd_reputation::Config for Test {
     type Event = Event;
+    type UpdateOrigin = EnsureRoot<AccountId>;
+    type OnRoundAborted = ();
+    type OnThresholdCrossed = ();
+    type Currency = Balances;
+    type WatchDeposit = WatchDeposit;
+    type MaxWatches = MaxWatches;
+}
+
+impl pallet_balances::Config for Test {
+    type MaxLocks = MaxLocks;
+    type Balance = Balance;
+    type Event = Event;
+    type DustRemoval = ();
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+    type WeightInfo = pallet_balances::weights::SubstrateWeight<Test>;
+}
+
+parameter_types! {
+    pub const MaxSeeds: u32 = 10;
+    pub const MaxSeedTerms: u32 = 2;
+    pub const SeedCooldown: u32 = 1;
+    pub const MaxMissedHeartbeats: u32 = 2;
 }
 
 impl zd_seeds::Config for Test {
     type Event = Event;
     type Reputation = ZdReputation;
+    type UpdateOrigin = EnsureRoot<AccountId>;
+    type MaxSeeds = MaxSeeds;
+    type MaxSeedTerms = MaxSeedTerms;
+    type SeedCooldown = SeedCooldown;
+    type MaxMissedHeartbeats = MaxMissedHeartbeats;
 }
 
 impl system::Config for Test {
@@ -89,20 +124,29 @@ impl system::Config for Test {
     type OnKilledAccount = ();
     type SystemWeightInfo = ();
     type SS58Prefix = SS58Prefix;
-    type AccountData = ();
+    type AccountData = pallet_balances::AccountData<Balance>;
 }
 
 pub struct ExtBuilder {
     period: BlockNumber,
+    seeds: Vec<AccountId>,
 }
 
 impl Default for ExtBuilder {
     fn default() -> Self {
-        Self { period: INIT_PERIOD }
+        Self {
+            period: INIT_PERIOD,
+            seeds: vec![],
+        }
     }
 }
 
 impl ExtBuilder {
+    pub fn seeds(mut self, seeds: Vec<AccountId>) -> Self {
+        self.seeds = seeds;
+        self
+    }
+
     pub fn build(self) -> sp_io::TestExternalities {
         let mut t = frame_system::GenesisConfig::default()
             .build_storage::<Test>()
@@ -114,6 +158,10 @@ impl ExtBuilder {
         .assimilate_storage(&mut t)
         .unwrap();
 
+        zd_seeds::GenesisConfig::<Test> { seeds: self.seeds }
+            .assimilate_storage(&mut t)
+            .unwrap();
+
         t.into()
     }
 }
@@ -20,7 +20,7 @@ use frame_support::{assert_noop, assert_ok, dispatch};
 
 fn initialize_seeds(seeds: Vec<<Test as system::Config>::AccountId>) {
     for seed in seeds.iter() {
-        <ZdSeeds as SeedsBase<_>>::add_seed(seed);
+        assert_ok!(<ZdSeeds as SeedsBase<_>>::add_seed(seed));
     }
 }
 
@@ -124,3 +124,269 @@ fn remove_seed_test() {
         );
     });
 }
+
+#[test]
+fn genesis_seeds_should_work() {
+    ExtBuilder::default()
+        .seeds(vec![BOB, ALICE, BOB])
+        .build()
+        .execute_with(|| {
+            assert_eq!(ZdSeeds::get_seed_count(), 2);
+            assert_eq!(ZdSeeds::is_seed(&ALICE), true);
+            assert_eq!(ZdSeeds::is_seed(&BOB), true);
+        });
+}
+
+#[test]
+fn add_seed_should_fail_over_max_seeds() {
+    new_test_ext().execute_with(|| {
+        initialize_seeds((1..=MaxSeeds::get() as u64).collect());
+        assert_noop!(
+            <ZdSeeds as SeedsBase<_>>::add_seed(&100u64),
+            Error::<Test>::TooManySeeds
+        );
+        assert_ok!(<ZdSeeds as SeedsBase<_>>::add_seed(&1u64));
+        assert_noop!(
+            ZdSeeds::new_seed(Origin::root(), 100u64),
+            Error::<Test>::TooManySeeds
+        );
+    });
+}
+
+#[test]
+fn set_seeds_should_work() {
+    new_test_ext().execute_with(|| {
+        initialize_seeds(vec![1u64, 2u64, 3u64]);
+        assert_noop!(
+            ZdSeeds::set_seeds(Origin::signed(ALICE), vec![ALICE]),
+            dispatch::DispatchError::BadOrigin
+        );
+        assert_ok!(ZdSeeds::set_seeds(
+            Origin::root(),
+            vec![5u64, 4u64, 5u64]
+        ));
+        assert_eq!(ZdSeeds::get_seed_count(), 2);
+        assert_eq!(ZdSeeds::is_seed(&1u64), false);
+        assert_eq!(ZdSeeds::is_seed(&4u64), true);
+
+        let seeds_event = Event::zd_seeds(crate::Event::SeedsReplaced(2));
+        assert!(System::events().iter().any(|record| record.event == seeds_event));
+
+        assert_noop!(
+            ZdSeeds::set_seeds(Origin::root(), (1..=MaxSeeds::get() as u64 + 1).collect()),
+            Error::<Test>::TooManySeeds
+        );
+
+        ZdReputation::set_step(&TIRStep::Reputation);
+        assert_noop!(
+            ZdSeeds::set_seeds(Origin::root(), vec![ALICE]),
+            Error::<Test>::StatusErr
+        );
+    });
+}
+
+#[test]
+fn seed_info_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(ZdSeeds::new_seed(Origin::root(), ALICE));
+        assert_ok!(<ZdSeeds as SeedsBase<_>>::add_computed_seed(&BOB, 20, &ALICE));
+        assert_eq!(
+            ZdSeeds::seed_info(&ALICE),
+            Some(SeedInfo {
+                nonce: 1,
+                score: 0,
+                pathfinder: None,
+                origin: SeedOrigin::Governance,
+            })
+        );
+        let bob_info = SeedInfo {
+            nonce: 1,
+            score: 20,
+            pathfinder: Some(ALICE),
+            origin: SeedOrigin::Computed,
+        };
+        assert_eq!(ZdSeeds::seed_info(&BOB), Some(bob_info.clone()));
+
+        assert_ok!(ZdSeeds::remove_seed(Origin::root(), ALICE));
+        assert_eq!(ZdSeeds::seed_info(&ALICE), None);
+
+        let infos = <ZdSeeds as SeedsBase<_>>::get_seed_infos();
+        assert_eq!(infos, vec![(BOB, bob_info.clone())]);
+        <ZdSeeds as SeedsBase<_>>::remove_all();
+        assert_eq!(ZdSeeds::seed_info(&BOB), None);
+
+        assert_ok!(<ZdSeeds as SeedsBase<_>>::restore_seeds(infos));
+        assert!(ZdSeeds::is_seed(&BOB));
+        assert_eq!(ZdSeeds::seed_info(&BOB), Some(bob_info));
+    });
+}
+
+#[test]
+fn archive_seeds_should_work() {
+    new_test_ext().execute_with(|| {
+        initialize_seeds(vec![1u64, 2u64]);
+        <ZdSeeds as SeedsBase<_>>::archive_seeds();
+        assert_eq!(ZdSeeds::seeds_at(1), vec![1u64, 2u64]);
+
+        assert_ok!(ZdSeeds::set_seeds(Origin::root(), vec![3u64]));
+        assert_eq!(ZdSeeds::seeds_at(1), vec![1u64, 2u64]);
+        assert!(ZdSeeds::seeds_at(2).is_empty());
+    });
+}
+
+fn set_round(nonce: u32) {
+    zd_reputation::SystemInfo::<Test>::mutate(|info| {
+        info.nonce = nonce;
+        info.step = TIRStep::Seed;
+    });
+}
+
+#[test]
+fn seed_term_should_work() {
+    new_test_ext().execute_with(|| {
+        initialize_seeds(vec![ALICE]);
+        set_round(1);
+        <ZdSeeds as SeedsBase<_>>::archive_seeds();
+        set_round(2);
+        assert!(<ZdSeeds as SeedsBase<_>>::is_eligible(&ALICE));
+        <ZdSeeds as SeedsBase<_>>::archive_seeds();
+        // Archiving a round again does not extend the term.
+        <ZdSeeds as SeedsBase<_>>::archive_seeds();
+        assert_eq!(ZdSeeds::seed_term(&ALICE), Some(SeedTerm { first: 1, last: 2 }));
+
+        set_round(3);
+        assert!(!<ZdSeeds as SeedsBase<_>>::is_eligible(&ALICE));
+        assert!(<ZdSeeds as SeedsBase<_>>::is_eligible(&BOB));
+
+        set_round(4);
+        assert!(<ZdSeeds as SeedsBase<_>>::is_eligible(&ALICE));
+        <ZdSeeds as SeedsBase<_>>::archive_seeds();
+        assert_eq!(ZdSeeds::seed_term(&ALICE), Some(SeedTerm { first: 4, last: 4 }));
+    });
+}
+
+#[test]
+fn drop_ineligible_should_work() {
+    new_test_ext().execute_with(|| {
+        initialize_seeds(vec![ALICE, BOB]);
+        set_round(1);
+        <ZdSeeds as SeedsBase<_>>::archive_seeds();
+        set_round(2);
+        assert_eq!(<ZdSeeds as SeedsBase<_>>::drop_ineligible(), 0);
+        <ZdSeeds as SeedsBase<_>>::archive_seeds();
+        assert_ok!(<ZdSeeds as SeedsBase<_>>::add_seed(&3u64));
+
+        // Kept seeds that reached the limit do not serve another round.
+        set_round(3);
+        assert_eq!(<ZdSeeds as SeedsBase<_>>::drop_ineligible(), 2);
+        <ZdSeeds as SeedsBase<_>>::archive_seeds();
+        assert_eq!(ZdSeeds::seeds_at(3), vec![3u64]);
+        assert_eq!(ZdSeeds::seed_term(&ALICE), Some(SeedTerm { first: 1, last: 2 }));
+        assert_eq!(ZdSeeds::seed_info(&ALICE), None);
+        let event = Event::zd_seeds(crate::Event::SeedRetired(ALICE));
+        assert!(System::events().iter().any(|record| record.event == event));
+    });
+}
+
+#[test]
+fn seed_heartbeat_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            ZdSeeds::seed_heartbeat(Origin::signed(ALICE)),
+            Error::<Test>::NotSeedUser
+        );
+        initialize_seeds(vec![ALICE, BOB]);
+        set_round(1);
+        <ZdSeeds as SeedsBase<_>>::archive_seeds();
+        // The first round of a term is not checked.
+        set_round(2);
+        <ZdSeeds as SeedsBase<_>>::drop_inactive();
+        assert_eq!(ZdSeeds::missed_heartbeats(&BOB), 0);
+        <ZdSeeds as SeedsBase<_>>::archive_seeds();
+
+        assert_ok!(ZdSeeds::seed_heartbeat(Origin::signed(ALICE)));
+        assert_noop!(
+            ZdSeeds::seed_heartbeat(Origin::signed(ALICE)),
+            Error::<Test>::AlreadyHeartbeat
+        );
+        let event = Event::zd_seeds(crate::Event::HeartbeatReceived(ALICE, 2));
+        assert!(System::events().iter().any(|record| record.event == event));
+
+        set_round(3);
+        <ZdSeeds as SeedsBase<_>>::drop_inactive();
+        // Checking a round twice does not count again.
+        <ZdSeeds as SeedsBase<_>>::drop_inactive();
+        assert_eq!(ZdSeeds::missed_heartbeats(&ALICE), 0);
+        assert_eq!(ZdSeeds::missed_heartbeats(&BOB), 1);
+        let event = Event::zd_seeds(crate::Event::HeartbeatMissed(BOB, 1));
+        assert!(System::events().iter().any(|record| record.event == event));
+        <ZdSeeds as SeedsBase<_>>::archive_seeds();
+
+        set_round(4);
+        <ZdSeeds as SeedsBase<_>>::drop_inactive();
+        assert_eq!(ZdSeeds::missed_heartbeats(&ALICE), 1);
+        assert!(ZdSeeds::is_seed(&ALICE));
+        assert!(!ZdSeeds::is_seed(&BOB));
+        assert_eq!(ZdSeeds::seed_info(&BOB), None);
+        let event = Event::zd_seeds(crate::Event::SeedDropped(BOB));
+        assert!(System::events().iter().any(|record| record.event == event));
+    });
+}
+
+#[test]
+fn heartbeat_while_free_should_count_once() {
+    new_test_ext().execute_with(|| {
+        initialize_seeds(vec![ALICE]);
+        set_round(1);
+        <ZdSeeds as SeedsBase<_>>::archive_seeds();
+        set_round(2);
+        <ZdSeeds as SeedsBase<_>>::archive_seeds();
+        zd_reputation::SystemInfo::<Test>::mutate(|info| info.step = TIRStep::Free);
+
+        // Counts for the finalised round only.
+        assert_ok!(ZdSeeds::seed_heartbeat(Origin::signed(ALICE)));
+        let event = Event::zd_seeds(crate::Event::HeartbeatReceived(ALICE, 2));
+        assert!(System::events().iter().any(|record| record.event == event));
+        set_round(3);
+        <ZdSeeds as SeedsBase<_>>::drop_inactive();
+        assert_eq!(ZdSeeds::missed_heartbeats(&ALICE), 0);
+        <ZdSeeds as SeedsBase<_>>::archive_seeds();
+
+        set_round(4);
+        <ZdSeeds as SeedsBase<_>>::drop_inactive();
+        assert_eq!(ZdSeeds::missed_heartbeats(&ALICE), 1);
+    });
+}
+
+#[test]
+fn undo_drop_inactive_should_work() {
+    new_test_ext().execute_with(|| {
+        initialize_seeds(vec![ALICE, BOB]);
+        set_round(1);
+        <ZdSeeds as SeedsBase<_>>::archive_seeds();
+        set_round(2);
+        <ZdSeeds as SeedsBase<_>>::archive_seeds();
+        set_round(3);
+        <ZdSeeds as SeedsBase<_>>::drop_inactive();
+        assert_ok!(ZdSeeds::seed_heartbeat(Origin::signed(ALICE)));
+        <ZdSeeds as SeedsBase<_>>::archive_seeds();
+
+        set_round(4);
+        let seeds = <ZdSeeds as SeedsBase<_>>::get_seed_infos();
+        <ZdSeeds as SeedsBase<_>>::drop_inactive();
+        assert!(!ZdSeeds::is_seed(&BOB));
+
+        // The round is aborted.
+        assert_ok!(<ZdSeeds as SeedsBase<_>>::restore_seeds(seeds));
+        <ZdSeeds as SeedsBase<_>>::undo_drop_inactive();
+        assert!(ZdSeeds::is_seed(&BOB));
+        assert_eq!(ZdSeeds::missed_heartbeats(&ALICE), 0);
+        assert_eq!(ZdSeeds::missed_heartbeats(&BOB), 1);
+        assert_eq!(ZdSeeds::checked_round(), 2);
+
+        // And started again.
+        <ZdSeeds as SeedsBase<_>>::drop_inactive();
+        assert!(ZdSeeds::is_seed(&ALICE));
+        assert!(!ZdSeeds::is_seed(&BOB));
+    });
+}
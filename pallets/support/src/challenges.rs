@@ -34,6 +34,9 @@ pub trait ChallengeBase<AccountId, AppId, Balance, BlockNumber> {
 
     /// Launch a challenge against `target` under `app_id`, `metadata` is used to
     /// set the initial challenge status.
+    ///
+    /// The `staking` of `metadata` must already be staked by its `pathfinder` for
+    /// `(app_id, target)`.
     fn launch(
         app_id: &AppId,
         target: &AccountId,
@@ -146,6 +149,10 @@ pub trait ChallengeBase<AccountId, AppId, Balance, BlockNumber> {
         target: &AccountId,
     ) -> Result<Option<u64>, DispatchError>;
 
+    /// Settle all challenges under `app_id` according to their current
+    /// `ChallengeStatus`, as `harvest` would without a sweeper, and remove them.
+    fn settle_all(app_id: &AppId) -> DispatchResult;
+
     /// Settle the current challenge. This is a low level operation.
     ///
 	/// When `restart` is `true`, the challenge will be set to the `Free` state,
@@ -13,7 +13,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-pub use reputation::Reputation;
+pub use reputation::{OnRoundAborted, OnThresholdCrossed, Reputation};
 pub use trust::TrustBase;
 pub use seeds::SeedsBase;
 pub use challenges::ChallengeBase;
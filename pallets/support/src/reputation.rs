@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use sp_runtime::DispatchResult;
+use zd_primitives::{merkle::ReputationProof, ReputationValue};
 
 pub trait Reputation<AccountId, BlockNumber, TIRStep> {
     /// The first nonce in `target` has a reputation value that is modified.
@@ -29,8 +30,29 @@ pub trait Reputation<AccountId, BlockNumber, TIRStep> {
     /// reputation value.
     fn get_reputation_new(target: &AccountId) -> Option<u32>;
 
-    /// Returns the latest verified reputation value of `target`.
-    fn get_reputation(target: &AccountId) -> Option<u32>;
+    /// Returns the latest verified reputation value of `target`, or the
+    /// provisional one vouched for by other accounts if it has none.
+    fn get_reputation(target: &AccountId) -> Option<ReputationValue>;
+
+    /// Returns the nonce of the latest round whose reputation is finalised.
+    fn get_finalized_nonce() -> u32;
+
+    /// Returns the reputation value of `target` finalised in round `nonce`, if
+    /// it is still stored.
+    fn get_reputation_at(target: &AccountId, nonce: &u32) -> Option<u32>;
+
+    /// Whether `proof` proves the reputation of `target` under the stored root
+    /// of round `proof.nonce`.
+    fn verify_reputation_proof(target: &AccountId, proof: &ReputationProof) -> bool;
+
+    /// Keeps the accounts and reputation of round `nonce` stored, and provable,
+    /// until `unpin_round` is called as many times.
+    fn pin_round(nonce: u32);
+
+    /// Releases a pin of round `nonce`, removing the round once it is no longer
+    /// pinned and older than the previous round. Returns the number of accounts
+    /// of the removed round.
+    fn unpin_round(nonce: u32) -> u32;
 
     /// Accepts a tuple of `AccountId`, `u32` and performs a refresh only if the
     /// user is not refreshed.
@@ -48,3 +70,32 @@ pub trait Reputation<AccountId, BlockNumber, TIRStep> {
     /// Start a new round.
     fn new_round() -> DispatchResult;
 }
+
+/// Handler for a round of the reputation system aborted by governance.
+pub trait OnRoundAborted {
+    /// Refunds or settles everything staked in the aborted round.
+    fn on_round_aborted() -> DispatchResult;
+}
+
+impl OnRoundAborted for () {
+    fn on_round_aborted() -> DispatchResult {
+        Ok(())
+    }
+}
+
+impl<A: OnRoundAborted, B: OnRoundAborted> OnRoundAborted for (A, B) {
+    fn on_round_aborted() -> DispatchResult {
+        A::on_round_aborted()?;
+        B::on_round_aborted()
+    }
+}
+
+/// Handler for a finalised reputation value crossing a watched threshold.
+pub trait OnThresholdCrossed<AccountId> {
+    /// The reputation of `who` moved from `old` to `new` across `threshold`.
+    fn on_threshold_crossed(who: &AccountId, threshold: u32, old: u32, new: u32);
+}
+
+impl<AccountId> OnThresholdCrossed<AccountId> for () {
+    fn on_threshold_crossed(_who: &AccountId, _threshold: u32, _old: u32, _new: u32) {}
+}
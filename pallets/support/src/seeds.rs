@@ -12,6 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use sp_runtime::DispatchResult;
+use sp_std::vec::Vec;
+use zd_primitives::SeedInfo;
+
 pub trait SeedsBase<AccountId> {
 
 	/// Returns whether `seed` is the seed user.
@@ -20,9 +24,46 @@ pub trait SeedsBase<AccountId> {
 	/// Returns the number of seed users.
 	fn get_seed_count() -> u32;
 
+	/// Returns the maximum number of seed users.
+	fn max_seed_count() -> u32;
+
+	/// Returns all seed users.
+	fn get_seeds() -> Vec<AccountId>;
+
+	/// Returns all seed users along with their metadata.
+	fn get_seed_infos() -> Vec<(AccountId, SeedInfo<AccountId>)>;
+
 	/// Empty all seeds.
 	fn remove_all();
 
-	/// Add a seed.
-	fn add_seed(new_seed: &AccountId);
+	/// Add a seed set by governance, or return `Err` if the maximum number of
+	/// seeds is reached.
+	fn add_seed(new_seed: &AccountId) -> DispatchResult;
+
+	/// Add a seed with centrality `score` submitted by `pathfinder`, or return
+	/// `Err` if the maximum number of seeds is reached.
+	fn add_computed_seed(new_seed: &AccountId, score: u64, pathfinder: &AccountId) -> DispatchResult;
+
+	/// Replace all seeds with `seeds`, keeping their metadata.
+	fn restore_seeds(seeds: Vec<(AccountId, SeedInfo<AccountId>)>) -> DispatchResult;
+
+	/// Drop the seeds that missed too many heartbeats, called when a round
+	/// starts.
+	fn drop_inactive();
+
+	/// Undo the heartbeat check of the round in progress, called once the seeds
+	/// it dropped are restored when the round is aborted.
+	fn undo_drop_inactive();
+
+	/// Whether `who` may be a seed candidate in the round in progress, given
+	/// the seed term limits.
+	fn is_eligible(who: &AccountId) -> bool;
+
+	/// Archive the current seeds as those of the round in progress, extending
+	/// their terms.
+	fn archive_seeds();
+
+	/// Drop the seeds that reached the seed term limit, called when the current
+	/// seeds are kept for the round in progress. Returns the number dropped.
+	fn drop_ineligible() -> u32;
 }
@@ -12,26 +12,73 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use sp_runtime::DispatchResult;
+use sp_runtime::{DispatchError, DispatchResult};
+use sp_std::vec::Vec;
+use zd_primitives::StakingId;
 
 pub trait MultiBaseToken<AccountId, Balance> {
+    /// The currency identifier.
+    type CurrencyId;
+
     /// Get the current `bonus` amount.
     fn get_bonus_amount() -> Balance;
 
-    /// `who` staking `amount`。
+    /// `who` staking `amount`, paid into the pool。
     fn staking(who: &AccountId, amount: &Balance) -> DispatchResult;
 
+    /// `who` stakes `amount` for `id`, recorded in the staking ledger.
+    ///
+    /// Depending on the implementation, the funds are paid into the pool or stay
+    /// on the account of `who`.
+    fn stake(who: &AccountId, id: &StakingId<AccountId>, amount: &Balance) -> DispatchResult;
+
+    /// Settle `amount` of the stake of `who` for `id` and return it to `who`.
+    fn unstake(who: &AccountId, id: &StakingId<AccountId>, amount: &Balance) -> DispatchResult;
+
+    /// Settle `amount` of the stake of `who` for `id` and hand it over to the pool,
+    /// from which it is released to others.
+    fn forfeit(who: &AccountId, id: &StakingId<AccountId>, amount: &Balance) -> DispatchResult;
+
+    /// `amount` of the stake of `from` for `id` is taken over by `to`, along with
+    /// its funds.
+    fn move_stake(
+        from: &AccountId,
+        to: &AccountId,
+        id: &StakingId<AccountId>,
+        amount: &Balance,
+    ) -> DispatchResult;
+
+    /// `amount` of the stake of `who` for `from` is recorded for `to` instead, its
+    /// funds stay where they are.
+    fn restake(
+        who: &AccountId,
+        from: &StakingId<AccountId>,
+        to: &StakingId<AccountId>,
+        amount: &Balance,
+    ) -> DispatchResult;
+
+    /// Returns the amount staked by `who` for `id`.
+    fn staked(who: &AccountId, id: &StakingId<AccountId>) -> Balance;
+
     /// Release the currency of `amount` to the account of `who`.
     fn release(who: &AccountId, amount: &Balance) -> DispatchResult;
 
     /// Returns the current `free_balance` of `who`.
     fn free_balance(who: &AccountId) -> Balance;
 
-    /// Returns the current `social_balance` of `who`.
-    fn social_balance(who: &AccountId) -> Balance;
+    /// Returns the current `social_balance` of `who` in `currency_id`.
+    fn social_balance(currency_id: Self::CurrencyId, who: &AccountId) -> Balance;
+
+    /// Returns the currencies `who` has a social balance in.
+    fn social_currencies(who: &AccountId) -> Vec<Self::CurrencyId>;
+
+    /// Returns the number of social currencies, an upper bound on what
+    /// `social_currencies` returns.
+    fn social_currency_count() -> u32;
 
-    /// Split `who`s social currency proportionally and return the fee amount.
-    fn share(who: &AccountId, target: &[AccountId]) -> Balance;
+    /// Split `who`s social currency in `currency_id` proportionally and return the
+    /// fee amount, which only currencies that fund staking pay.
+    fn share(currency_id: Self::CurrencyId, who: &AccountId, target: &[AccountId]) -> Balance;
 
     /// `who` injects `bonus` in the amount of `amount` into the pool, prioritising 
     /// the deduction of `pending` from `who`.
@@ -43,19 +90,47 @@ pub trait MultiBaseToken<AccountId, Balance> {
     /// of amounts themselves.
     fn cut_bonus(amount: &Balance) -> DispatchResult;
 
-    /// Returns the `actual_balance` of `who`, including `pending`, `social` and `free`.
-    fn actual_balance(who: &AccountId) -> Balance;
+    /// Returns the `actual_balance` of `who` in `currency_id`, including `pending`,
+    /// `social` and `free`.
+    fn actual_balance(currency_id: Self::CurrencyId, who: &AccountId) -> Balance;
 
-    /// Returns the `pending_balance` of `who`.
-    fn pending_balance(who: &AccountId) -> Balance;
+    /// Returns the `pending_balance` of `who` in `currency_id`.
+    fn pending_balance(currency_id: Self::CurrencyId, who: &AccountId) -> Balance;
 
-    /// `from` transfers currency in the amount of `amount` to the social account of `to`.
-    fn transfer_social(from: &AccountId, to: &AccountId, amount: Balance) -> DispatchResult;
+    /// `from` transfers `currency_id` in the amount of `amount` to the social account
+    /// of `to`.
+    fn transfer_social(
+        currency_id: Self::CurrencyId,
+        from: &AccountId,
+        to: &AccountId,
+        amount: Balance,
+    ) -> DispatchResult;
 
     /// Preference is given to currencies that use `pending` from `from` to transfer `amount` 
     /// amounts to `SocialPool` accounts.
     fn pay_with_pending(from: &AccountId, amount: Balance) -> DispatchResult;
 
-    /// Take the `pending` out of `who`.
-    fn claim(who: &AccountId) -> DispatchResult;
+    /// Put the social currency ratios set by governance in force, and record
+    /// those in force for round `nonce`.
+    fn start_round(nonce: u32);
+
+    /// Take the `pending` in `currency_id` out of `who`, which fails while part of it
+    /// is still vesting.
+    fn claim(currency_id: Self::CurrencyId, who: &AccountId) -> DispatchResult;
+
+    /// Take the part of the `pending` in `currency_id` that has vested out of `who`, and
+    /// return the amount taken.
+    fn claim_vested(
+        currency_id: Self::CurrencyId,
+        who: &AccountId,
+    ) -> Result<Balance, DispatchError>;
+
+    /// Take `amount` of the vested `pending` in `currency_id` out of `who` into the
+    /// account of `dest`.
+    fn claim_to(
+        currency_id: Self::CurrencyId,
+        who: &AccountId,
+        dest: &AccountId,
+        amount: Balance,
+    ) -> DispatchResult;
 }
@@ -34,6 +34,10 @@ pub trait TrustBase<AccountId> {
     /// Returns the user trusted by `who` before the refresh started.
     fn get_trust_old(who: &AccountId) -> Vec<AccountId>;
 
+    /// Returns the users `who` trusts, each with the level of trust in them, which is
+    /// their computed reputation.
+    fn get_trust_levels(who: &AccountId) -> Vec<(AccountId, u32)>;
+
     /// Returns the total length of the `users` path as a tuple, which is passed 
     /// to the end user's reputation value, the first user is not the seed user, 
     /// or an error in the path will return `Error`.
@@ -42,3 +46,40 @@ pub trait TrustBase<AccountId> {
     /// `Ok` will be returned if the path is correct.
     fn valid_nodes(nodes: &[AccountId]) -> DispatchResult;
 }
+
+/// No one trusts anyone, so there are no paths.
+impl<AccountId> TrustBase<AccountId> for () {
+    fn remove_all_tmp() {}
+
+    fn get_trust_count(_who: &AccountId) -> usize {
+        0
+    }
+
+    fn get_trust_count_old(_who: &AccountId) -> usize {
+        0
+    }
+
+    fn is_trust(_who: &AccountId, _target: &AccountId) -> bool {
+        false
+    }
+
+    fn is_trust_old(_who: &AccountId, _target: &AccountId) -> bool {
+        false
+    }
+
+    fn get_trust_old(_who: &AccountId) -> Vec<AccountId> {
+        Vec::new()
+    }
+
+    fn get_trust_levels(_who: &AccountId) -> Vec<(AccountId, u32)> {
+        Vec::new()
+    }
+
+    fn computed_path(_users: &[AccountId]) -> Result<(u32, u32), DispatchError> {
+        Err(DispatchError::Other("No trust relationships"))
+    }
+
+    fn valid_nodes(_nodes: &[AccountId]) -> DispatchResult {
+        Err(DispatchError::Other("No trust relationships"))
+    }
+}
@@ -0,0 +1,42 @@
+// Copyright 2021 ZeroDAO
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime API definition for the tokens module.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::too_many_arguments)]
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+use zd_primitives::{Balance, PoolAccounting, StakingId};
+
+sp_api::decl_runtime_apis! {
+    pub trait TokensApi<AccountId, CurrencyId> where
+        AccountId: Codec,
+        CurrencyId: Codec,
+    {
+        /// All stakes of `who` in the staking ledger.
+        fn stakes_of(who: AccountId) -> Vec<(StakingId<AccountId>, Balance)>;
+
+        /// Amount staked by `who` for `id`.
+        fn staked(who: AccountId, id: StakingId<AccountId>) -> Balance;
+
+        /// The account of the social pool.
+        fn social_pool() -> AccountId;
+
+        /// What the social pool holds in `currency_id`, and what it holds it for.
+        fn pool_accounting(currency_id: CurrencyId) -> PoolAccounting;
+    }
+}
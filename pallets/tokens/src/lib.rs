@@ -19,6 +19,32 @@
 //! The ZdToken module is used to manage social currency of users, staking and system rewards.
 //! All funds are held in a `SocialPool` rather than being sent to the user in real time, 
 //! which is more efficient for social currency and staking, which require frequent interaction. 
+//! The `SocialPool` account is derived from the `ModuleId` of the module.
+//!
+//! Social accounts are kept per currency, so that communities can have their own social
+//! token on top of the same trust graph. Social balances can only be sent in the
+//! `SocialCurrencies` chosen by the runtime. Only `BaceToken` funds staking and the bonus, so
+//! when another currency is shared, the parts that would pay the fee and the bonus are
+//! reserved to its owner instead.
+//!
+//! With a `VestingPeriod`, what `share` reserves to the owner's `pending` unlocks linearly
+//! over that many blocks, and only the unlocked part can be claimed or paid with.
+//!
+//! With a `DemurrageRate`, social balances in `BaceToken` decay by that rate every
+//! `DemurragePeriod` blocks they sit idle, and what decays goes to the bonus. This is applied
+//! lazily, when the balance is next shared or transferred to.
+//!
+//! Stakes are recorded in a ledger by account and `StakingId`, so that each stake is settled
+//! against what was staked for it. With `StakingMode::Reserve`, stakes are reserved on the
+//! staker's own account instead of being paid into the `SocialPool`, and only move when
+//! they are forfeited or taken over.
+//!
+//! In every currency, the `SocialPool` must always cover the `pending` and `social` balances
+//! of all social accounts, and in `BaceToken` also `TotalStaking`, of which `Bonus` is a
+//! part. The module keeps running totals of the social accounts so that this can be checked
+//! every `SolvencyCheckPeriod` blocks,
+//! and checks it against the social accounts themselves after runtime upgrades when built
+//! with `try-runtime`.
 //!
 //! ### Implementations
 //!
@@ -26,32 +52,59 @@
 //!
 //! - `MultiBaseToken` - Application management of system currency.
 //!
+//! `ToBonus` is an `OnUnbalanced` handler that sends part of what it is given, such as
+//! transaction fees or treasury burns, to the bonus.
+//!
 //! ## Interface
 //!
 //! ### Dispatchable Functions
 //!
-//! - `transfer_social` - An interface for sending social currency to a particular user.
-//! - `claim` - The user withdraws the funds from `pending` to the balance.
+//! - `transfer_social` - An interface for sending social currency in a given currency to a
+//!   particular user.
+//! - `transfer_social_batch` - Send social currency in a given currency to many users at once.
+//! - `transfer_social_to_trustees` - Split an amount of social currency across the users the
+//!   caller trusts, evenly or weighted by the level of trust in them.
+//! - `claim` - The user withdraws the funds from `pending` in a given currency to the balance.
+//! - `claim_vested` - The user withdraws the vested part of `pending` in a given currency to
+//!   the balance.
+//! - `claim_to` - The user withdraws part of the vested `pending` in a given currency to the
+//!   balance of another account.
+//! - `set_social_ratios` - Set the ratios social currency is shared with from the
+//!   next round, requires `UpdateOrigin`.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
 
-use frame_support::{pallet_prelude::*, transactional};
+use frame_support::{
+    debug,
+    pallet_prelude::*,
+    traits::{BalanceStatus, Currency, Imbalance, OnUnbalanced},
+    transactional,
+};
 use frame_system::{ensure_signed, pallet_prelude::*};
 use sp_runtime::{
-    traits::{MaybeSerializeDeserialize, Member, Saturating, StaticLookup, Zero},
-    DispatchResult,
+    traits::{
+        AccountIdConversion, AtLeast32BitUnsigned, MaybeSerializeDeserialize, Member,
+        SaturatedConversion, Saturating, StaticLookup, Zero,
+    },
+    helpers_128bit::multiply_by_rational,
+    DispatchError, DispatchResult, ModuleId, Perbill,
+};
+use sp_std::{
+    collections::{btree_map::BTreeMap, btree_set::BTreeSet},
+    convert::{TryFrom, TryInto},
+    vec::Vec,
 };
-use sp_std::convert::{TryFrom, TryInto};
 
-use zd_primitives::{per_social_currency, Balance};
-use zd_support::MultiBaseToken;
+use zd_primitives::{Balance, PoolAccounting, StakingId};
+use zd_support::{MultiBaseToken, TrustBase};
 
 use orml_traits::{
     arithmetic::{self, Signed},
-    MultiCurrency,
+    MultiCurrency, MultiReservableCurrency,
 };
 
+pub mod migrations;
 mod mock;
 mod tests;
 pub mod weights;
@@ -59,6 +112,10 @@ pub mod weights;
 pub use module::*;
 pub use weights::WeightInfo;
 
+/// The maximum number of vesting tranches of a `pending` balance, past which what
+/// vests is added to the latest tranche.
+pub const MAX_VESTING_TRANCHES: usize = 16;
+
 /// balance information for an account.
 #[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
 pub struct SocialAccount<Balance> {
@@ -84,6 +141,109 @@ impl<Balance: Saturating + Copy + Ord> SocialAccount<Balance> {
     }
 }
 
+/// A tranche of a `pending` balance that unlocks linearly from `start`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+pub struct VestingSchedule<BlockNumber> {
+    /// The block the vesting starts at.
+    pub start: BlockNumber,
+    /// Amount locked at `start`.
+    #[codec(compact)]
+    pub locked: Balance,
+}
+
+impl<BlockNumber: AtLeast32BitUnsigned + Copy> VestingSchedule<BlockNumber> {
+    /// Amount still locked at `now`, if it unlocks over `period` blocks.
+    pub fn locked_at(&self, now: BlockNumber, period: BlockNumber) -> Balance {
+        let elapsed = now.saturating_sub(self.start);
+        if elapsed >= period {
+            return Zero::zero();
+        }
+        let vested = Perbill::from_rational_approximation(elapsed, period) * self.locked;
+        self.locked.saturating_sub(vested)
+    }
+}
+
+/// How a social balance is split when it is shared, the rest goes to the bonus.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct SocialRatios {
+    /// Reserved to the owner's `pending` balance.
+    pub reserved: Perbill,
+    /// Transferred to the social currency of users trusted by the owner.
+    pub share: Perbill,
+    /// Burned.
+    pub burn: Perbill,
+    /// Pathfinder's fee.
+    pub fee: Perbill,
+    /// The share is divided by at least this number of trusted users.
+    pub min_trust_count: u32,
+}
+
+impl Default for SocialRatios {
+    fn default() -> Self {
+        SocialRatios {
+            reserved: Perbill::from_percent(10),
+            share: Perbill::from_percent(10),
+            burn: Perbill::from_percent(10),
+            fee: Perbill::from_percent(10),
+            min_trust_count: 150,
+        }
+    }
+}
+
+impl SocialRatios {
+    /// Whether the ratios sum to at most 100% and `min_trust_count` is not zero.
+    pub fn is_valid(&self) -> bool {
+        let total = [self.reserved, self.share, self.burn, self.fee]
+            .iter()
+            .map(|ratio| ratio.deconstruct() as u64)
+            .sum::<u64>();
+        total <= Perbill::one().deconstruct() as u64 && self.min_trust_count > 0
+    }
+}
+
+/// Where a social balance went when it was shared.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Default, RuntimeDebug)]
+pub struct SharedAmounts {
+    /// Transferred to the social currency of each trustee.
+    pub per_trustee: Balance,
+    /// Number of trustees the share was transferred to.
+    pub trustees: u32,
+    /// Left as the owner's social balance.
+    pub remaining: Balance,
+    /// Reserved to the owner's `pending` balance.
+    pub reserved: Balance,
+    /// Burned.
+    pub burned: Balance,
+    /// Pathfinder's fee.
+    pub fee: Balance,
+    /// Added to the bonus.
+    pub bonus: Balance,
+}
+
+/// Storage releases of the module.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, RuntimeDebug)]
+pub enum Releases {
+    /// Social accounts by account only, in a pool held by the runtime.
+    V1_0_0,
+    /// Social accounts by currency, in a pool derived from `ModuleId`.
+    V2_0_0,
+}
+
+impl Default for Releases {
+    fn default() -> Self {
+        Releases::V1_0_0
+    }
+}
+
+/// Where the funds of stakes are held.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum StakingMode {
+    /// Paid into the `SocialPool`.
+    Transfer,
+    /// Reserved on the staker's own account.
+    Reserve,
+}
+
 #[frame_support::pallet]
 pub mod module {
 
@@ -106,19 +266,60 @@ pub mod module {
             + Copy
             + MaybeSerializeDeserialize;
 
-        type Currency: MultiCurrency<
+        type Currency: MultiReservableCurrency<
             Self::AccountId,
             CurrencyId = Self::CurrencyId,
             Balance = Balance,
         >;
 
-        /// Which currency to use.
+        /// The currency that funds staking and the bonus.
         #[pallet::constant]
         type BaceToken: Get<Self::CurrencyId>;
 
-        /// Address of the pool.
+        /// The currencies social balances can be sent in.
+        type SocialCurrencies: Get<Vec<Self::CurrencyId>>;
+
+        /// The module id, used for deriving the account of the pool.
+        #[pallet::constant]
+        type ModuleId: Get<ModuleId>;
+
+        /// The origin which may set the social currency ratios.
+        type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+        /// Where the funds of stakes are held.
+        ///
+        /// NOTE: Must not be changed while there are stakes in the ledger.
+        #[pallet::constant]
+        type StakingMode: Get<StakingMode>;
+
+        /// How often, in blocks, the solvency of the pool is checked, zero to never check.
+        #[pallet::constant]
+        type SolvencyCheckPeriod: Get<Self::BlockNumber>;
+
+        /// How many blocks what `share` reserves to `pending` takes to unlock, zero to
+        /// unlock at once.
+        #[pallet::constant]
+        type VestingPeriod: Get<Self::BlockNumber>;
+
+        /// How much of a social balance in `BaceToken` decays every `DemurragePeriod`,
+        /// zero for none.
+        #[pallet::constant]
+        type DemurrageRate: Get<Perbill>;
+
+        /// How many blocks a social balance takes to decay by `DemurrageRate`.
+        #[pallet::constant]
+        type DemurragePeriod: Get<Self::BlockNumber>;
+
+        /// The trust relationships social currency can be sent along.
+        type TrustBase: TrustBase<Self::AccountId>;
+
+        /// The maximum number of users anyone can trust.
+        #[pallet::constant]
+        type MaxTrustCount: Get<u32>;
+
+        /// The maximum number of transfers in one `transfer_social_batch`.
         #[pallet::constant]
-        type SocialPool: Get<Self::AccountId>;
+        type MaxBatch: Get<u32>;
 
         /// Weight information for extrinsics in this module.
         type WeightInfo: WeightInfo;
@@ -131,36 +332,191 @@ pub mod module {
         BonusTooLow,
         /// Total staking amount too low
         StakingAmountTooLow,
+        /// The ratios sum to more than 100%, or the minimum trust count is zero
+        InvalidRatios,
+        /// Staked amount too low
+        StakeTooLow,
+        /// Reserved balance too low
+        ReserveTooLow,
+        /// The caller trusts no one
+        NoTrustees,
+        /// None of the trustees has a level of trust
+        NoTrustLevel,
+        /// Part of the pending balance is still vesting
+        PendingLocked,
+        /// Pending balance too low
+        PendingTooLow,
+        /// The currency cannot be used as a social currency
+        NotSocialCurrency,
+        /// More transfers than `MaxBatch` in one batch
+        TooManyTransfers,
     }
 
     #[pallet::event]
     #[pallet::generate_deposit(pub(crate) fn deposit_event)]
     pub enum Event<T: Config> {
-        /// Token transfer_social success. \[from, to, amount\]
-        TransferSocial(T::AccountId, T::AccountId, Balance),
-        /// Transferr `pending` Tokens to `free` \[who\]
-        Claim(T::AccountId),
+        /// Token transfer_social success. \[currency_id, from, to, amount\]
+        TransferSocial(T::CurrencyId, T::AccountId, T::AccountId, Balance),
+        /// Transferr `pending` Tokens to `free` \[currency_id, who\]
+        Claim(T::CurrencyId, T::AccountId),
+        /// Transferr the vested part of `pending` to `free` \[currency_id, who, amount\]
+        ClaimVested(T::CurrencyId, T::AccountId, Balance),
+        /// Transferr `pending` Tokens to the `free` balance of `dest`.
+        /// \[currency_id, who, dest, amount\]
+        ClaimTo(T::CurrencyId, T::AccountId, T::AccountId, Balance),
+        /// Social currency ratios set, in force from the next round. \[ratios\]
+        SocialRatiosSet(SocialRatios),
+        /// Social currency ratios in force for round `nonce`. \[nonce, ratios\]
+        SocialRatiosApplied(u32, SocialRatios),
+        /// The pool does not cover what it owes in `currency_id`. \[currency_id, accounting\]
+        Insolvent(T::CurrencyId, PoolAccounting),
+        /// The social balance of `who` was shared. \[currency_id, who, amounts\]
+        Shared(T::CurrencyId, T::AccountId, SharedAmounts),
+        /// The bonus was increased by `who`. \[who, amount\]
+        BonusIncreased(T::AccountId, Balance),
+        /// The bonus was cut. \[amount\]
+        BonusCut(Balance),
+        /// Staked funds were released from the pool to `who`. \[who, amount\]
+        Released(T::AccountId, Balance),
+        /// The social balance of `who` decayed, and `amount` went to the bonus. \[who, amount\]
+        Demurrage(T::AccountId, Balance),
+        /// `amount` of fees or burns went to the bonus. \[amount\]
+        BonusFunded(Balance),
     }
 
     #[pallet::pallet]
     pub struct Pallet<T>(PhantomData<T>);
 
     #[pallet::hooks]
-    impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+    impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+        fn on_initialize(now: T::BlockNumber) -> Weight {
+            let period = T::SolvencyCheckPeriod::get();
+            if period.is_zero() || !(now % period).is_zero() {
+                return 0;
+            }
+            let currencies = Self::currencies();
+            for currency_id in currencies.iter() {
+                let accounting = Self::pool_accounting(*currency_id);
+                if !accounting.is_solvent() {
+                    debug::error!("The social pool is insolvent: {:?}", accounting);
+                    Self::deposit_event(Event::Insolvent(*currency_id, accounting));
+                }
+            }
+            T::DbWeight::get().reads(5 * currencies.len() as Weight + 2)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade() -> Result<(), &'static str> {
+            Self::check_solvency()
+        }
+    }
+
+    #[pallet::extra_constants]
+    impl<T: Config> Pallet<T> {
+        /// The account of the pool.
+        pub fn social_pool() -> T::AccountId {
+            T::ModuleId::get().into_account()
+        }
+    }
+
+    #[pallet::genesis_config]
+    #[derive(Default)]
+    pub struct GenesisConfig {}
+
+    #[pallet::genesis_build]
+    impl<T: Config> GenesisBuild<T> for GenesisConfig {
+        fn build(&self) {
+            StorageVersion::<T>::put(Releases::V2_0_0);
+        }
+    }
+
+    /// Storage version of the module, used by `migrations`.
+    #[pallet::storage]
+    #[pallet::getter(fn storage_version)]
+    pub type StorageVersion<T: Config> = StorageValue<_, Releases, ValueQuery>;
 
     #[pallet::storage]
     #[pallet::getter(fn get_bonus)]
     pub type Bonus<T: Config> = StorageValue<_, Balance, ValueQuery>;
 
+    /// The social account of `AccountId` in `CurrencyId`.
     #[pallet::storage]
     #[pallet::getter(fn accounts)]
-    pub type Accounts<T: Config> =
-        StorageMap<_, Twox64Concat, T::AccountId, SocialAccount<Balance>, ValueQuery>;
+    pub type SocialAccounts<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        T::AccountId,
+        Twox64Concat,
+        T::CurrencyId,
+        SocialAccount<Balance>,
+        ValueQuery,
+    >;
 
     #[pallet::storage]
     #[pallet::getter(fn total_staking)]
     pub type TotalStaking<T: Config> = StorageValue<_, Balance, ValueQuery>;
 
+    /// Sum of `pending` of all social accounts in `CurrencyId`.
+    #[pallet::storage]
+    #[pallet::getter(fn total_pending)]
+    pub type TotalPending<T: Config> =
+        StorageMap<_, Twox64Concat, T::CurrencyId, Balance, ValueQuery>;
+
+    /// Sum of `social` of all social accounts in `CurrencyId`.
+    #[pallet::storage]
+    #[pallet::getter(fn total_social)]
+    pub type TotalSocial<T: Config> =
+        StorageMap<_, Twox64Concat, T::CurrencyId, Balance, ValueQuery>;
+
+    /// The tranches of the `pending` of `AccountId` in `CurrencyId` that are vesting,
+    /// oldest first.
+    #[pallet::storage]
+    #[pallet::getter(fn vesting)]
+    pub type PendingVesting<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        T::AccountId,
+        Twox64Concat,
+        T::CurrencyId,
+        Vec<VestingSchedule<T::BlockNumber>>,
+        ValueQuery,
+    >;
+
+    /// The block demurrage was last applied to the social balance of `AccountId` at.
+    #[pallet::storage]
+    #[pallet::getter(fn last_touched)]
+    pub type LastTouched<T: Config> =
+        StorageMap<_, Twox64Concat, T::AccountId, T::BlockNumber, OptionQuery>;
+
+    /// Amount staked by `AccountId` for `StakingId`.
+    #[pallet::storage]
+    #[pallet::getter(fn staked_for)]
+    pub type StakingLedger<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        T::AccountId,
+        Twox64Concat,
+        StakingId<T::AccountId>,
+        Balance,
+        ValueQuery,
+    >;
+
+    /// The ratios social currency is currently shared with.
+    #[pallet::storage]
+    #[pallet::getter(fn social_ratios)]
+    pub type Ratios<T: Config> = StorageValue<_, SocialRatios, ValueQuery>;
+
+    /// Ratios set by governance, in force from the next round.
+    #[pallet::storage]
+    #[pallet::getter(fn next_ratios)]
+    pub type NextRatios<T: Config> = StorageValue<_, SocialRatios, OptionQuery>;
+
+    /// The ratios in force for round `nonce`.
+    #[pallet::storage]
+    #[pallet::getter(fn ratios_at)]
+    pub type RoundRatios<T: Config> =
+        StorageMap<_, Twox64Concat, u32, SocialRatios, OptionQuery>;
+
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         /// Transfer some balance to another social-currency account
@@ -171,81 +527,529 @@ pub mod module {
         #[transactional]
         pub fn transfer_social(
             origin: OriginFor<T>,
+            currency_id: T::CurrencyId,
             dest: <T::Lookup as StaticLookup>::Source,
             #[pallet::compact] amount: Balance,
         ) -> DispatchResultWithPostInfo {
             let from = ensure_signed(origin)?;
             let to = T::Lookup::lookup(dest)?;
-            <Self as MultiBaseToken<_, _>>::transfer_social(&from, &to, amount)?;
-            Self::deposit_event(Event::TransferSocial(from, to, amount));
+            <Self as MultiBaseToken<_, _>>::transfer_social(currency_id, &from, &to, amount)?;
+            Self::deposit_event(Event::TransferSocial(currency_id, from, to, amount));
+            Ok(().into())
+        }
+
+        /// Transfer some balance to many other social-currency accounts, one
+        /// `(dest, amount)` pair each.
+        ///
+        /// The total is paid from the caller's `pending` first, and there can be no
+        /// more than `MaxBatch` transfers.
+        ///
+        /// The dispatch origin for this call must be `Signed` by the
+        /// transactor.
+        #[pallet::weight(T::WeightInfo::transfer_social_batch(
+            (transfers.len() as u32).min(T::MaxBatch::get())
+        ))]
+        #[transactional]
+        pub fn transfer_social_batch(
+            origin: OriginFor<T>,
+            currency_id: T::CurrencyId,
+            transfers: Vec<(<T::Lookup as StaticLookup>::Source, Balance)>,
+        ) -> DispatchResultWithPostInfo {
+            let from = ensure_signed(origin)?;
+            ensure!(
+                transfers.len() <= T::MaxBatch::get() as usize,
+                Error::<T>::TooManyTransfers
+            );
+            let transfers = transfers
+                .into_iter()
+                .map(|(dest, amount)| Ok((T::Lookup::lookup(dest)?, amount)))
+                .collect::<Result<Vec<_>, DispatchError>>()?;
+            Self::do_transfer_social_batch(currency_id, &from, &transfers)?;
             Ok(().into())
         }
 
-        /// Extract the caller `pending` to the `free` balance.
+        /// Split `amount` across the social-currency accounts of the users the
+        /// caller trusts, in proportion to the level of trust in them if
+        /// `weighted`, or evenly otherwise.
+        ///
+        /// The amount is paid from the caller's `pending` first, and what cannot
+        /// be split exactly, rounding each share down, stays with the caller.
+        /// Trustees whose share rounds down to zero are skipped.
+        ///
+        /// The dispatch origin for this call must be `Signed` by the
+        /// transactor.
+        #[pallet::weight(T::WeightInfo::transfer_social_to_trustees(T::MaxTrustCount::get()))]
+        #[transactional]
+        pub fn transfer_social_to_trustees(
+            origin: OriginFor<T>,
+            currency_id: T::CurrencyId,
+            #[pallet::compact] amount: Balance,
+            weighted: bool,
+        ) -> DispatchResultWithPostInfo {
+            let from = ensure_signed(origin)?;
+            let trustees = T::TrustBase::get_trust_levels(&from);
+            ensure!(!trustees.is_empty(), Error::<T>::NoTrustees);
+            let count = trustees.len() as u32;
+            let shares: Vec<(T::AccountId, Balance)> = match weighted {
+                true => {
+                    let total_level = trustees.iter().map(|(_, level)| *level as u64).sum::<u64>();
+                    ensure!(!total_level.is_zero(), Error::<T>::NoTrustLevel);
+                    trustees
+                        .into_iter()
+                        .filter(|(_, level)| *level > 0)
+                        .map(|(trustee, level)| {
+                            let share =
+                                multiply_by_rational(amount, level as u128, total_level as u128)
+                                    .unwrap_or_else(|_| Zero::zero());
+                            (trustee, share)
+                        })
+                        .collect()
+                }
+                false => {
+                    let share = amount / count as Balance;
+                    trustees
+                        .into_iter()
+                        .map(|(trustee, _)| (trustee, share))
+                        .collect()
+                }
+            };
+            let transfers: Vec<(T::AccountId, Balance)> = shares
+                .into_iter()
+                .filter(|(_, share)| !share.is_zero())
+                .collect();
+            Self::do_transfer_social_batch(currency_id, &from, &transfers)?;
+            Ok(Some(T::WeightInfo::transfer_social_to_trustees(count)).into())
+        }
+
+        /// Extract the caller `pending` in `currency_id` to the `free` balance.
         #[pallet::weight(T::WeightInfo::claim())]
         #[transactional]
-        pub fn claim(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+        pub fn claim(
+            origin: OriginFor<T>,
+            currency_id: T::CurrencyId,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            <Self as MultiBaseToken<_, _>>::claim(currency_id, &who)?;
+            Self::deposit_event(Event::Claim(currency_id, who));
+            Ok(().into())
+        }
+
+        /// Extract the vested part of the caller `pending` in `currency_id` to the
+        /// `free` balance.
+        #[pallet::weight(T::WeightInfo::claim_vested())]
+        #[transactional]
+        pub fn claim_vested(
+            origin: OriginFor<T>,
+            currency_id: T::CurrencyId,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let amount = <Self as MultiBaseToken<_, _>>::claim_vested(currency_id, &who)?;
+            Self::deposit_event(Event::ClaimVested(currency_id, who, amount));
+            Ok(().into())
+        }
+
+        /// Extract `amount` of the vested caller `pending` in `currency_id` to the
+        /// `free` balance of `dest`.
+        #[pallet::weight(T::WeightInfo::claim_to())]
+        #[transactional]
+        pub fn claim_to(
+            origin: OriginFor<T>,
+            currency_id: T::CurrencyId,
+            dest: <T::Lookup as StaticLookup>::Source,
+            #[pallet::compact] amount: Balance,
+        ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
-            <Self as MultiBaseToken<_, _>>::claim(&who)?;
-            Self::deposit_event(Event::Claim(who));
+            let dest = T::Lookup::lookup(dest)?;
+            <Self as MultiBaseToken<_, _>>::claim_to(currency_id, &who, &dest, amount)?;
+            Self::deposit_event(Event::ClaimTo(currency_id, who, dest, amount));
+            Ok(().into())
+        }
+
+        /// Set the ratios social currency is shared with, in force from the next
+        /// round.
+        ///
+        /// The dispatch origin for this call must be `UpdateOrigin`.
+        #[pallet::weight(T::WeightInfo::set_social_ratios())]
+        pub fn set_social_ratios(
+            origin: OriginFor<T>,
+            ratios: SocialRatios,
+        ) -> DispatchResultWithPostInfo {
+            T::UpdateOrigin::ensure_origin(origin)?;
+            ensure!(ratios.is_valid(), Error::<T>::InvalidRatios);
+            NextRatios::<T>::put(ratios);
+            Self::deposit_event(Event::SocialRatiosSet(ratios));
             Ok(().into())
         }
     }
 }
 
 impl<T: Config> Pallet<T> {
-    /// Set social balance of `who` to a new value.
+    /// What the pool holds and owes in `currency_id`, from the running totals.
+    pub fn pool_accounting(currency_id: T::CurrencyId) -> PoolAccounting {
+        let (staking, bonus) = match currency_id == T::BaceToken::get() {
+            true => (Self::total_staking(), Self::get_bonus()),
+            false => (Zero::zero(), Zero::zero()),
+        };
+        PoolAccounting {
+            pool: T::Currency::free_balance(currency_id, &Self::social_pool()),
+            pending: Self::total_pending(currency_id),
+            social: Self::total_social(currency_id),
+            staking,
+            bonus,
+        }
+    }
+
+    /// `BaceToken` and the currencies of social accounts.
+    fn currencies() -> BTreeSet<T::CurrencyId> {
+        let mut currencies = TotalSocial::<T>::iter()
+            .chain(TotalPending::<T>::iter())
+            .map(|(currency_id, _)| currency_id)
+            .collect::<BTreeSet<_>>();
+        currencies.insert(T::BaceToken::get());
+        currencies
+    }
+
+    /// Check that the running totals match the social accounts, and that the pool
+    /// covers what it owes in every currency.
+    ///
+    /// Iterates over all social accounts, so it is not meant to be used on chain.
+    pub fn check_solvency() -> Result<(), &'static str> {
+        let mut totals = BTreeMap::<T::CurrencyId, (Balance, Balance)>::new();
+        for (_, currency_id, account) in SocialAccounts::<T>::iter() {
+            let (pending, social) = totals.entry(currency_id).or_default();
+            *pending = pending.saturating_add(account.pending);
+            *social = social.saturating_add(account.social);
+        }
+        for currency_id in Self::currencies() {
+            let (pending, social) = totals.get(&currency_id).copied().unwrap_or_default();
+            let accounting = Self::pool_accounting(currency_id);
+            ensure!(
+                accounting.pending == pending && accounting.social == social,
+                "Running totals do not match the social accounts"
+            );
+            ensure!(accounting.is_solvent(), "The social pool is insolvent");
+        }
+        Ok(())
+    }
+
+    /// Mutate the social account of `who` in `currency_id`, keeping the running totals
+    /// in step.
+    fn mutate_account<R>(
+        currency_id: T::CurrencyId,
+        who: &T::AccountId,
+        f: impl FnOnce(&mut SocialAccount<Balance>) -> R,
+    ) -> R {
+        <SocialAccounts<T>>::mutate(who, currency_id, |account| {
+            let old_account = account.clone();
+            let result = f(account);
+            <TotalPending<T>>::mutate(currency_id, |total| {
+                *total = total
+                    .saturating_sub(old_account.pending)
+                    .saturating_add(account.pending)
+            });
+            <TotalSocial<T>>::mutate(currency_id, |total| {
+                *total = total
+                    .saturating_sub(old_account.social)
+                    .saturating_add(account.social)
+            });
+            result
+        })
+    }
+
+    /// Set social balance of `who` in `currency_id` to a new value.
     ///
     /// Note this will not maintain total issuance, and the caller is
     /// expected to do it.
-    pub(crate) fn set_social_balance(who: &T::AccountId, amount: Balance) {
-        <Accounts<T>>::mutate(who, |account| {
+    pub(crate) fn set_social_balance(
+        currency_id: T::CurrencyId,
+        who: &T::AccountId,
+        amount: Balance,
+    ) {
+        Self::mutate_account(currency_id, who, |account| {
             account.social = amount;
         });
     }
 
+    /// Returns the amount transferred to each trustee and the remaining social balance
+    /// of `from`.
     pub(crate) fn share_and_reserv(
+        currency_id: T::CurrencyId,
         from: &T::AccountId,
         trustees: &[T::AccountId],
         total_share_amount: Balance,
         reserved_amount: Balance,
-    ) {
+    ) -> (Balance, Balance) {
+        let mut per_trustee: Balance = Zero::zero();
         let mut remaining_share: Balance = Zero::zero();
         if !trustees.is_empty() && total_share_amount != 0 {
             if let Some(share_amount) = total_share_amount.checked_div(
                 (trustees.len() as u32)
-                    .max(per_social_currency::MIN_TRUST_COUNT)
+                    .max(Self::social_ratios().min_trust_count)
                     .into(),
             ) {
                 trustees.iter().for_each(|trustee| {
-                    <Accounts<T>>::mutate(trustee, |account| {
+                    Self::touch(currency_id, trustee);
+                    Self::mutate_account(currency_id, trustee, |account| {
                         account.social = account.social.saturating_add(share_amount);
                     });
                 });
 
                 remaining_share = total_share_amount
                     .saturating_sub(share_amount.saturating_mul(trustees.len() as Balance));
+                per_trustee = share_amount;
             } else {
                 remaining_share = total_share_amount;
             }
         }
 
-        <Accounts<T>>::mutate(from, |account| {
+        Self::mutate_account(currency_id, from, |account| {
             account.social = remaining_share;
-            account.pending = reserved_amount;
+            account.pending = account.pending.saturating_add(reserved_amount);
         });
+        (per_trustee, remaining_share)
     }
 
-    /// Set pending balance of `who` to a new value.
+    /// Set pending balance of `who` in `currency_id` to a new value.
     ///
     /// Note this will not maintain total issuance, and the caller is
     /// expected to do it.
-    pub fn set_pending_balance(who: &T::AccountId, amount: Balance) {
-        <Accounts<T>>::mutate(who, |account| {
+    pub fn set_pending_balance(currency_id: T::CurrencyId, who: &T::AccountId, amount: Balance) {
+        Self::mutate_account(currency_id, who, |account| {
             account.pending = amount;
         });
     }
 
+    /// The part of the `pending` of `who` in `currency_id` that is still vesting.
+    pub fn locked_pending(currency_id: T::CurrencyId, who: &T::AccountId) -> Balance {
+        let now = frame_system::Module::<T>::block_number();
+        let period = T::VestingPeriod::get();
+        Self::vesting(who, currency_id)
+            .iter()
+            .fold(Zero::zero(), |locked: Balance, schedule| {
+                locked.saturating_add(schedule.locked_at(now, period))
+            })
+    }
+
+    /// The part of the `pending` of `who` in `currency_id` that can be claimed or paid with.
+    pub fn unlocked_pending(currency_id: T::CurrencyId, who: &T::AccountId) -> Balance {
+        Self::pending_balance(currency_id, who)
+            .saturating_sub(Self::locked_pending(currency_id, who))
+    }
+
+    /// Lock `amount` of the `pending` of `who` in `currency_id` in a new tranche that
+    /// vests from now, leaving the older tranches as they are.
+    ///
+    /// Tranches that have vested are dropped, and past `MAX_VESTING_TRANCHES` the
+    /// amount is added to the latest tranche instead.
+    fn vest(currency_id: T::CurrencyId, who: &T::AccountId, amount: Balance) {
+        let period = T::VestingPeriod::get();
+        if period.is_zero() || amount.is_zero() {
+            return;
+        }
+        let now = frame_system::Module::<T>::block_number();
+        PendingVesting::<T>::mutate(who, currency_id, |schedules| {
+            schedules.retain(|schedule| !schedule.locked_at(now, period).is_zero());
+            let full = schedules.len() >= MAX_VESTING_TRANCHES;
+            match schedules.last_mut() {
+                Some(last) if full || last.start == now => {
+                    last.locked = last.locked.saturating_add(amount);
+                }
+                _ => schedules.push(VestingSchedule {
+                    start: now,
+                    locked: amount,
+                }),
+            }
+        });
+    }
+
+    /// Transfer `amount` of the `pending` of `who` in `currency_id` from the pool to
+    /// `dest`, which must not be more than has vested.
+    fn do_claim(
+        currency_id: T::CurrencyId,
+        who: &T::AccountId,
+        dest: &T::AccountId,
+        amount: Balance,
+    ) -> DispatchResult {
+        let pending_balance = Self::pending_balance(currency_id, who)
+            .checked_sub(amount)
+            .ok_or(Error::<T>::PendingTooLow)?;
+        let locked = Self::locked_pending(currency_id, who);
+        ensure!(pending_balance >= locked, Error::<T>::PendingLocked);
+        if locked.is_zero() {
+            PendingVesting::<T>::remove(who, currency_id);
+        }
+        T::Currency::transfer(currency_id, &Self::social_pool(), dest, amount)?;
+        Self::set_pending_balance(currency_id, who, pending_balance);
+        Ok(())
+    }
+
+    /// Apply the demurrage accrued on the social balance of `who` in `BaceToken` since
+    /// it was last touched, and return the decayed amount, which goes to the bonus.
+    ///
+    /// Only whole `DemurragePeriod`s are applied, the rest is carried over.
+    pub fn apply_demurrage(who: &T::AccountId) -> Balance {
+        let rate = T::DemurrageRate::get();
+        let period = T::DemurragePeriod::get();
+        if rate.is_zero() || period.is_zero() {
+            return Zero::zero();
+        }
+        let now = frame_system::Module::<T>::block_number();
+        let currency_id = T::BaceToken::get();
+        let social_balance = Self::social_balance(currency_id, who);
+        // An empty balance has nothing to decay, the clock starts again when funds arrive.
+        let last = match Self::last_touched(who) {
+            Some(last) if !social_balance.is_zero() => last,
+            _ => {
+                LastTouched::<T>::insert(who, now);
+                return Zero::zero();
+            }
+        };
+        let periods = now.saturating_sub(last) / period;
+        if periods.is_zero() {
+            return Zero::zero();
+        }
+        LastTouched::<T>::insert(who, last.saturating_add(periods.saturating_mul(period)));
+
+        let kept = rate
+            .left_from_one()
+            .saturating_pow(periods.saturated_into::<u32>() as usize)
+            * social_balance;
+        let decayed = social_balance.saturating_sub(kept);
+        if !decayed.is_zero() {
+            Self::set_social_balance(currency_id, who, kept);
+            // Like the rest of the bonus, it is paid out with `release`.
+            Self::do_staking(&decayed);
+            Self::add_bonus(&decayed);
+            Self::deposit_event(Event::Demurrage(who.clone(), decayed));
+        }
+        decayed
+    }
+
+    /// Apply demurrage to `who` before its social balance in `currency_id` changes.
+    fn touch(currency_id: T::CurrencyId, who: &T::AccountId) {
+        if currency_id == T::BaceToken::get() {
+            Self::apply_demurrage(who);
+        }
+    }
+
+    /// Returns all stakes of `who` in the staking ledger.
+    pub fn stakes_of(who: &T::AccountId) -> Vec<(StakingId<T::AccountId>, Balance)> {
+        StakingLedger::<T>::iter_prefix(who).collect()
+    }
+
+    fn add_stake(
+        who: &T::AccountId,
+        id: &StakingId<T::AccountId>,
+        amount: &Balance,
+    ) -> DispatchResult {
+        StakingLedger::<T>::try_mutate(who, id, |staked| -> DispatchResult {
+            *staked = staked.checked_add(*amount).ok_or(Error::<T>::Overflow)?;
+            Ok(())
+        })
+    }
+
+    fn sub_stake(
+        who: &T::AccountId,
+        id: &StakingId<T::AccountId>,
+        amount: &Balance,
+    ) -> DispatchResult {
+        StakingLedger::<T>::try_mutate_exists(who, id, |maybe_staked| -> DispatchResult {
+            let staked = maybe_staked
+                .unwrap_or_default()
+                .checked_sub(*amount)
+                .ok_or(Error::<T>::StakeTooLow)?;
+            *maybe_staked = if staked.is_zero() { None } else { Some(staked) };
+            Ok(())
+        })
+    }
+
+    /// Reserve `amount` on the account of `who`, prioritising the withdrawal of `pending`
+    /// from the pool.
+    fn reserve_with_pending(who: &T::AccountId, amount: Balance) -> DispatchResult {
+        let currency_id = T::BaceToken::get();
+        let pending_balance = Self::pending_balance(currency_id, who);
+        let from_pending = Self::unlocked_pending(currency_id, who).min(amount);
+        if !from_pending.is_zero() {
+            T::Currency::transfer(currency_id, &Self::social_pool(), who, from_pending)?;
+            Self::set_pending_balance(currency_id, who, pending_balance - from_pending);
+        }
+        T::Currency::reserve(currency_id, who, amount)
+    }
+
+    /// Pay `amount` of `currency_id` into the pool, prioritising the deduction of
+    /// the unlocked `pending` from `from`.
+    fn do_pay_with_pending(
+        currency_id: T::CurrencyId,
+        from: &T::AccountId,
+        amount: Balance,
+    ) -> DispatchResult {
+        let form_pending_balance = Self::pending_balance(currency_id, from);
+        let from_pending = Self::unlocked_pending(currency_id, from).min(amount);
+        if from_pending < amount {
+            T::Currency::transfer(
+                currency_id,
+                from,
+                &Self::social_pool(),
+                amount - from_pending,
+            )?;
+        }
+        Self::set_pending_balance(currency_id, from, form_pending_balance - from_pending);
+        Ok(())
+    }
+
+    /// Ensure that `currency_id` is one of the `SocialCurrencies`.
+    fn ensure_social_currency(currency_id: T::CurrencyId) -> DispatchResult {
+        ensure!(
+            T::SocialCurrencies::get().contains(&currency_id),
+            Error::<T>::NotSocialCurrency
+        );
+        Ok(())
+    }
+
+    /// Transfer social currency from `from` to each `(to, amount)` of `transfers`,
+    /// paying the total with `pending` first.
+    fn do_transfer_social_batch(
+        currency_id: T::CurrencyId,
+        from: &T::AccountId,
+        transfers: &[(T::AccountId, Balance)],
+    ) -> DispatchResult {
+        Self::ensure_social_currency(currency_id)?;
+        let total = transfers
+            .iter()
+            .try_fold(Balance::zero(), |total, (_, amount)| {
+                total.checked_add(*amount)
+            })
+            .ok_or(Error::<T>::Overflow)?;
+        Self::do_pay_with_pending(currency_id, from, total)?;
+        for (to, amount) in transfers {
+            Self::touch(currency_id, to);
+            let to_social_balance = Self::social_balance(currency_id, to)
+                .checked_add(*amount)
+                .ok_or(Error::<T>::Overflow)?;
+            Self::set_social_balance(currency_id, to, to_social_balance);
+            Self::deposit_event(Event::TransferSocial(
+                currency_id,
+                from.clone(),
+                to.clone(),
+                *amount,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Move `amount` reserved on the account of `from` to `to`, as `status`.
+    fn repatriate(
+        from: &T::AccountId,
+        to: &T::AccountId,
+        amount: Balance,
+        status: BalanceStatus,
+    ) -> DispatchResult {
+        let remaining =
+            T::Currency::repatriate_reserved(T::BaceToken::get(), from, to, amount, status)?;
+        ensure!(remaining.is_zero(), Error::<T>::ReserveTooLow);
+        Ok(())
+    }
+
     pub(crate) fn do_staking(amount: &Balance) {
         <TotalStaking<T>>::mutate(|t| *t = t.saturating_add(*amount));
     }
@@ -275,31 +1079,78 @@ impl<T: Config> Pallet<T> {
     }
 }
 
+/// Sends `Part` of an imbalance to the bonus, and the rest to `Rest`.
+///
+/// `C` must be the currency of `BaceToken`.
+pub struct ToBonus<T, C, Part, Rest>(PhantomData<(T, C, Part, Rest)>);
+
+impl<T, C, Part, Rest> OnUnbalanced<C::NegativeImbalance> for ToBonus<T, C, Part, Rest>
+where
+    T: Config,
+    C: Currency<T::AccountId, Balance = Balance>,
+    Part: Get<Perbill>,
+    Rest: OnUnbalanced<C::NegativeImbalance>,
+{
+    fn on_nonzero_unbalanced(amount: C::NegativeImbalance) {
+        let to_bonus = Part::get() * amount.peek();
+        let (to_bonus, rest) = amount.split(to_bonus);
+        let value = to_bonus.peek();
+        C::resolve_creating(&Pallet::<T>::social_pool(), to_bonus);
+        if !value.is_zero() {
+            // Like the rest of the bonus, it is paid out with `release`.
+            Pallet::<T>::do_staking(&value);
+            Pallet::<T>::add_bonus(&value);
+            Pallet::<T>::deposit_event(Event::BonusFunded(value));
+        }
+        Rest::on_unbalanced(rest);
+    }
+}
+
 impl<T: Config> MultiBaseToken<T::AccountId, Balance> for Pallet<T> {
+    type CurrencyId = T::CurrencyId;
+
     fn get_bonus_amount() -> Balance {
         Self::get_bonus()
     }
 
-    fn actual_balance(who: &T::AccountId) -> Balance {
-        let free_balance = T::Currency::free_balance(T::BaceToken::get(), who);
-        free_balance.saturating_add(Self::accounts(who).total())
+    fn actual_balance(currency_id: T::CurrencyId, who: &T::AccountId) -> Balance {
+        let free_balance = T::Currency::free_balance(currency_id, who);
+        free_balance.saturating_add(Self::accounts(who, currency_id).total())
+    }
+
+    fn pending_balance(currency_id: T::CurrencyId, who: &T::AccountId) -> Balance {
+        Self::accounts(who, currency_id).pending
+    }
+
+    fn social_balance(currency_id: T::CurrencyId, who: &T::AccountId) -> Balance {
+        Self::accounts(who, currency_id).social
     }
 
-    fn pending_balance(who: &T::AccountId) -> Balance {
-        Self::accounts(who).pending
+    fn social_currencies(who: &T::AccountId) -> Vec<T::CurrencyId> {
+        SocialAccounts::<T>::iter_prefix(who)
+            .filter(|(_, account)| !account.social.is_zero())
+            .map(|(currency_id, _)| currency_id)
+            .collect()
     }
 
-    fn social_balance(who: &T::AccountId) -> Balance {
-        Self::accounts(who).social
+    fn social_currency_count() -> u32 {
+        T::SocialCurrencies::get().len() as u32
     }
 
     #[transactional]
-    fn transfer_social(from: &T::AccountId, to: &T::AccountId, amount: Balance) -> DispatchResult {
-        let to_social_balance = Self::social_balance(to)
+    fn transfer_social(
+        currency_id: T::CurrencyId,
+        from: &T::AccountId,
+        to: &T::AccountId,
+        amount: Balance,
+    ) -> DispatchResult {
+        Self::ensure_social_currency(currency_id)?;
+        Self::touch(currency_id, to);
+        let to_social_balance = Self::social_balance(currency_id, to)
             .checked_add(amount)
             .ok_or(Error::<T>::Overflow)?;
-        Self::pay_with_pending(from, amount)?;
-        Self::set_social_balance(to, to_social_balance);
+        Self::do_pay_with_pending(currency_id, from, amount)?;
+        Self::set_social_balance(currency_id, to, to_social_balance);
         Ok(())
     }
 
@@ -311,32 +1162,87 @@ impl<T: Config> MultiBaseToken<T::AccountId, Balance> for Pallet<T> {
     }
 
     #[transactional]
-    fn pay_with_pending(from: &T::AccountId, amount: Balance) -> DispatchResult {
-        let form_pending_balance = Self::pending_balance(from);
-        match form_pending_balance >= amount {
-            true => {
-                Self::set_pending_balance(from, form_pending_balance - amount);
-            }
-            false => {
-                T::Currency::transfer(
-                    T::BaceToken::get(),
-                    from,
-                    &T::SocialPool::get(),
-                    amount - form_pending_balance,
-                )?;
-                Self::set_pending_balance(from, Zero::zero());
+    fn stake(who: &T::AccountId, id: &StakingId<T::AccountId>, amount: &Balance) -> DispatchResult {
+        match T::StakingMode::get() {
+            StakingMode::Transfer => Self::staking(who, amount)?,
+            StakingMode::Reserve => Self::reserve_with_pending(who, *amount)?,
+        }
+        Self::add_stake(who, id, amount)
+    }
+
+    #[transactional]
+    fn unstake(
+        who: &T::AccountId,
+        id: &StakingId<T::AccountId>,
+        amount: &Balance,
+    ) -> DispatchResult {
+        Self::sub_stake(who, id, amount)?;
+        match T::StakingMode::get() {
+            StakingMode::Transfer => Self::release(who, amount),
+            StakingMode::Reserve => {
+                let remaining = T::Currency::unreserve(T::BaceToken::get(), who, *amount);
+                ensure!(remaining.is_zero(), Error::<T>::ReserveTooLow);
+                Ok(())
             }
         }
+    }
+
+    #[transactional]
+    fn forfeit(
+        who: &T::AccountId,
+        id: &StakingId<T::AccountId>,
+        amount: &Balance,
+    ) -> DispatchResult {
+        Self::sub_stake(who, id, amount)?;
+        if T::StakingMode::get() == StakingMode::Reserve {
+            Self::repatriate(who, &Self::social_pool(), *amount, BalanceStatus::Free)?;
+            Self::do_staking(amount);
+        }
         Ok(())
     }
 
+    #[transactional]
+    fn move_stake(
+        from: &T::AccountId,
+        to: &T::AccountId,
+        id: &StakingId<T::AccountId>,
+        amount: &Balance,
+    ) -> DispatchResult {
+        Self::sub_stake(from, id, amount)?;
+        if T::StakingMode::get() == StakingMode::Reserve {
+            Self::repatriate(from, to, *amount, BalanceStatus::Reserved)?;
+        }
+        Self::add_stake(to, id, amount)
+    }
+
+    #[transactional]
+    fn restake(
+        who: &T::AccountId,
+        from: &StakingId<T::AccountId>,
+        to: &StakingId<T::AccountId>,
+        amount: &Balance,
+    ) -> DispatchResult {
+        Self::sub_stake(who, from, amount)?;
+        Self::add_stake(who, to, amount)
+    }
+
+    fn staked(who: &T::AccountId, id: &StakingId<T::AccountId>) -> Balance {
+        Self::staked_for(who, id)
+    }
+
+    #[transactional]
+    fn pay_with_pending(from: &T::AccountId, amount: Balance) -> DispatchResult {
+        Self::do_pay_with_pending(T::BaceToken::get(), from, amount)
+    }
+
     #[transactional]
     fn release(who: &T::AccountId, amount: &Balance) -> DispatchResult {
         let total_staking = Self::total_staking()
             .checked_sub(*amount)
             .ok_or(Error::<T>::StakingAmountTooLow)?;
-        T::Currency::transfer(T::BaceToken::get(), &T::SocialPool::get(), who, *amount)?;
+        T::Currency::transfer(T::BaceToken::get(), &Self::social_pool(), who, *amount)?;
         <TotalStaking<T>>::put(total_staking);
+        Self::deposit_event(Event::Released(who.clone(), *amount));
         Ok(())
     }
 
@@ -344,13 +1250,15 @@ impl<T: Config> MultiBaseToken<T::AccountId, Balance> for Pallet<T> {
         T::Currency::free_balance(T::BaceToken::get(), who)
     }
 
-    fn share(who: &T::AccountId, targets: &[T::AccountId]) -> Balance {
-        let social_balance = Self::social_balance(who);
+    fn share(currency_id: T::CurrencyId, who: &T::AccountId, targets: &[T::AccountId]) -> Balance {
+        Self::touch(currency_id, who);
+        let social_balance = Self::social_balance(currency_id, who);
+        let ratios = Self::social_ratios();
 
-        let total_share_amount = per_social_currency::PRE_SHARE.mul_floor(social_balance);
-        let reserved_amount = per_social_currency::PRE_RESERVED.mul_floor(social_balance);
-        let burn_amount = per_social_currency::PRE_BURN.mul_floor(social_balance);
-        let fee_amount = per_social_currency::PRE_FEE.mul_floor(social_balance);
+        let total_share_amount = ratios.share.mul_floor(social_balance);
+        let reserved_amount = ratios.reserved.mul_floor(social_balance);
+        let burn_amount = ratios.burn.mul_floor(social_balance);
+        let fee_amount = ratios.fee.mul_floor(social_balance);
 
         let pre_reward = social_balance
             .saturating_sub(total_share_amount)
@@ -358,34 +1266,102 @@ impl<T: Config> MultiBaseToken<T::AccountId, Balance> for Pallet<T> {
             .saturating_sub(burn_amount)
             .saturating_sub(fee_amount);
 
-        let _ = T::Currency::slash(T::BaceToken::get(), &T::SocialPool::get(), burn_amount);
+        let unburned = T::Currency::slash(currency_id, &Self::social_pool(), burn_amount);
+
+        // Only `BaceToken` funds staking, the fee and the bonus of other currencies
+        // are reserved to `who`.
+        let (reserved_amount, fee_amount, pre_reward) = match currency_id == T::BaceToken::get() {
+            true => (reserved_amount, fee_amount, pre_reward),
+            false => (
+                reserved_amount
+                    .saturating_add(fee_amount)
+                    .saturating_add(pre_reward),
+                Zero::zero(),
+                Zero::zero(),
+            ),
+        };
 
-        Self::share_and_reserv(who, targets, total_share_amount, reserved_amount);
-        Self::do_staking(&fee_amount);
+        let (per_trustee, remaining) = Self::share_and_reserv(
+            currency_id,
+            who,
+            targets,
+            total_share_amount,
+            reserved_amount,
+        );
+        Self::vest(currency_id, who, reserved_amount);
+        // The bonus is paid out with `release`, so it is staked as well.
+        Self::do_staking(&fee_amount.saturating_add(pre_reward));
         Self::add_bonus(&pre_reward);
+        Self::deposit_event(Event::Shared(
+            currency_id,
+            who.clone(),
+            SharedAmounts {
+                per_trustee,
+                trustees: targets.len() as u32,
+                remaining,
+                reserved: reserved_amount,
+                burned: burn_amount.saturating_sub(unburned),
+                fee: fee_amount,
+                bonus: pre_reward,
+            },
+        ));
         fee_amount
     }
 
     #[transactional]
     fn increase_bonus(who: &T::AccountId, amount: &Balance) -> DispatchResult {
         Self::staking(who, amount)?;
-        Self::try_add_bonus(amount)
+        Self::try_add_bonus(amount)?;
+        Self::deposit_event(Event::BonusIncreased(who.clone(), *amount));
+        Ok(())
     }
 
     fn cut_bonus(amount: &Balance) -> DispatchResult {
-        Self::try_cut_bonus(amount)
+        Self::try_cut_bonus(amount)?;
+        Self::deposit_event(Event::BonusCut(*amount));
+        Ok(())
     }
 
-    fn claim(who: &T::AccountId) -> DispatchResult {
-        <Accounts<T>>::try_mutate(who, |account| -> DispatchResult {
-            T::Currency::transfer(
-                T::BaceToken::get(),
-                &T::SocialPool::get(),
-                who,
-                account.pending,
-            )?;
-            account.pending = Zero::zero();
-            Ok(())
-        })
+    fn start_round(nonce: u32) {
+        if let Some(ratios) = NextRatios::<T>::take() {
+            Ratios::<T>::put(ratios);
+        }
+        let ratios = Self::social_ratios();
+        RoundRatios::<T>::insert(nonce, ratios);
+        Self::deposit_event(Event::SocialRatiosApplied(nonce, ratios));
+    }
+
+    fn claim(currency_id: T::CurrencyId, who: &T::AccountId) -> DispatchResult {
+        ensure!(
+            Self::locked_pending(currency_id, who).is_zero(),
+            Error::<T>::PendingLocked
+        );
+        PendingVesting::<T>::remove(who, currency_id);
+        T::Currency::transfer(
+            currency_id,
+            &Self::social_pool(),
+            who,
+            Self::pending_balance(currency_id, who),
+        )?;
+        Self::set_pending_balance(currency_id, who, Zero::zero());
+        Ok(())
+    }
+
+    fn claim_vested(
+        currency_id: T::CurrencyId,
+        who: &T::AccountId,
+    ) -> Result<Balance, DispatchError> {
+        let amount = Self::unlocked_pending(currency_id, who);
+        Self::do_claim(currency_id, who, who, amount)?;
+        Ok(amount)
+    }
+
+    fn claim_to(
+        currency_id: T::CurrencyId,
+        who: &T::AccountId,
+        dest: &T::AccountId,
+        amount: Balance,
+    ) -> DispatchResult {
+        Self::do_claim(currency_id, who, dest, amount)
     }
 }
@@ -0,0 +1,142 @@
+// Copyright 2021 ZeroDAO
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Storage migrations for the ZdToken module.
+
+use crate::*;
+#[cfg(feature = "try-runtime")]
+use frame_support::storage::{with_transaction, TransactionOutcome};
+use frame_support::{
+    debug, storage::migration::storage_key_iter, traits::PalletInfo, weights::Weight,
+};
+
+/// Migrate the module from `Releases::V1_0_0` to `Releases::V2_0_0`, with the pool
+/// held by `old_pool` before.
+///
+/// If the pool can not be moved, nothing is changed and the version is left as it was,
+/// so the migration is tried again with the next upgrade. Run `pre_migrate_to_v2` with
+/// `try-runtime` to catch this before the upgrade is enacted.
+pub fn migrate_to_v2<T: Config>(old_pool: &T::AccountId) -> Weight {
+    if Pallet::<T>::storage_version() != Releases::V1_0_0 {
+        return T::DbWeight::get().reads(1);
+    }
+
+    let weight = match migrate_social_pool::<T>(old_pool) {
+        Ok(weight) => weight,
+        Err(e) => {
+            debug::error!("Failed to move the social pool: {:?}", e);
+            return T::DbWeight::get().reads(3);
+        }
+    }
+    .saturating_add(init_social_totals::<T>())
+    .saturating_add(stake_shared_bonus::<T>());
+
+    StorageVersion::<T>::put(Releases::V2_0_0);
+    weight.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+}
+
+/// Check that `migrate_to_v2` can move the pool from `old_pool`, without changing
+/// anything.
+#[cfg(feature = "try-runtime")]
+pub fn pre_migrate_to_v2<T: Config>(old_pool: &T::AccountId) -> Result<(), &'static str> {
+    if Pallet::<T>::storage_version() != Releases::V1_0_0 {
+        return Ok(());
+    }
+    with_transaction(|| TransactionOutcome::Rollback(migrate_social_pool::<T>(old_pool)))
+        .map(|_| ())
+        .map_err(|_| "The social pool can not be moved")
+}
+
+/// Move the social accounts, which used to be kept in `Accounts` by account only, to
+/// `SocialAccounts` in `BaceToken`.
+///
+/// Does nothing once `Accounts` is empty, so it is safe to run more than once.
+pub fn migrate_social_accounts<T: Config>() -> Weight {
+    let pallet_name = match <T as frame_system::Config>::PalletInfo::name::<Pallet<T>>() {
+        Some(name) => name,
+        None => return 0,
+    };
+    let currency_id = T::BaceToken::get();
+    let old_accounts = storage_key_iter::<T::AccountId, SocialAccount<Balance>, Twox64Concat>(
+        pallet_name.as_bytes(),
+        b"Accounts",
+    )
+    .drain()
+    .collect::<Vec<_>>();
+    let count = old_accounts.len() as Weight;
+    for (who, account) in old_accounts {
+        SocialAccounts::<T>::insert(who, currency_id, account);
+    }
+    T::DbWeight::get().reads_writes(count, count.saturating_mul(2))
+}
+
+/// Move the pool from `old_pool` to the account derived from `ModuleId`.
+///
+/// The free balance of `old_pool` is transferred to the new pool. The social accounts
+/// of `old_pool` are its owner's, and are left as they are.
+///
+/// Does nothing once `old_pool` is empty, so it is safe to run more than once.
+pub fn migrate_social_pool<T: Config>(old_pool: &T::AccountId) -> Result<Weight, DispatchError> {
+    let new_pool = Pallet::<T>::social_pool();
+    if *old_pool == new_pool {
+        return Ok(T::DbWeight::get().reads(1));
+    }
+
+    let amount = T::Currency::free_balance(T::BaceToken::get(), old_pool);
+    if !amount.is_zero() {
+        T::Currency::transfer(T::BaceToken::get(), old_pool, &new_pool, amount)?;
+    }
+
+    Ok(T::DbWeight::get().reads_writes(3, 2))
+}
+
+/// Set `TotalPending` and `TotalSocial` from the social accounts.
+///
+/// Iterates over all social accounts, so it is only run once, by `migrate_to_v2`.
+pub fn init_social_totals<T: Config>() -> Weight {
+    let mut count: Weight = 0;
+    let mut totals = BTreeMap::<T::CurrencyId, (Balance, Balance)>::new();
+    for (_, currency_id, account) in SocialAccounts::<T>::iter() {
+        let (pending, social) = totals.entry(currency_id).or_default();
+        *pending = pending.saturating_add(account.pending);
+        *social = social.saturating_add(account.social);
+        count = count.saturating_add(1);
+    }
+    TotalPending::<T>::drain().for_each(drop);
+    TotalSocial::<T>::drain().for_each(drop);
+    for (currency_id, (pending, social)) in totals {
+        TotalPending::<T>::insert(currency_id, pending);
+        TotalSocial::<T>::insert(currency_id, social);
+        count = count.saturating_add(2);
+    }
+    T::DbWeight::get().reads_writes(count, count)
+}
+
+/// Count in `TotalStaking` the bonus `share` added before it was staked.
+///
+/// That bonus was added to `Bonus` and held in the pool, but not counted in
+/// `TotalStaking`. What the pool holds beyond the social accounts and `TotalStaking`,
+/// up to `Bonus`, is counted as staked. Must be run after `init_social_totals`.
+pub fn stake_shared_bonus<T: Config>() -> Weight {
+    let currency_id = T::BaceToken::get();
+    let total_staking = Pallet::<T>::total_staking();
+    let owed = Pallet::<T>::total_pending(currency_id)
+        .saturating_add(Pallet::<T>::total_social(currency_id))
+        .saturating_add(total_staking);
+    let unstaked = T::Currency::free_balance(currency_id, &Pallet::<T>::social_pool())
+        .saturating_sub(owed)
+        .min(Pallet::<T>::get_bonus());
+    TotalStaking::<T>::put(total_staking.saturating_add(unstaked));
+    T::DbWeight::get().reads_writes(5, 1)
+}
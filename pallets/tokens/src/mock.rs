@@ -15,13 +15,17 @@
 #![cfg(test)]
 
 use crate as zd_tokens;
-use frame_support::{construct_runtime, parameter_types, traits::GenesisBuild};
+use crate::StakingMode;
+use frame_support::{construct_runtime, parameter_types, traits::{GenesisBuild, Get}};
 use frame_support::sp_runtime::{
     testing::Header,
     traits::{BlakeTwo256, IdentityLookup, Zero},
+    DispatchError, DispatchResult, ModuleId, Perbill,
 };
 use frame_system as system;
 use sp_core::H256;
+use std::{cell::RefCell, collections::BTreeMap};
+use zd_support::TrustBase;
 
 use orml_traits::parameter_type_with_key;
 use orml_currencies::BasicCurrencyAdapter;
@@ -135,7 +139,122 @@ impl pallet_balances::Config for Test {
 }
 
 parameter_types! {
-    pub const SocialPoolAccountId: AccountId = 10000;
+    pub const ZdTokenModuleId: ModuleId = ModuleId(*b"zd/socpl");
+    pub const SolvencyCheckPeriod: BlockNumber = 10;
+    pub const MaxTrustCount: u32 = 600;
+    pub const MaxBatch: u32 = 3;
+    pub const DemurragePeriod: BlockNumber = 10;
+    pub SocialCurrencies: Vec<CurrencyId> = vec![ORS, DOT];
+    pub const FeesToBonusRatio: Perbill = Perbill::from_percent(20);
+}
+
+pub type OrsCurrency = orml_tokens::CurrencyAdapter<Test, BaceToken>;
+pub type FeesToBonus = zd_tokens::ToBonus<Test, OrsCurrency, FeesToBonusRatio, ()>;
+
+thread_local! {
+    static STAKING_MODE: RefCell<StakingMode> = RefCell::new(StakingMode::Transfer);
+    static VESTING_PERIOD: RefCell<BlockNumber> = RefCell::new(0);
+    static DEMURRAGE_RATE: RefCell<Perbill> = RefCell::new(Perbill::zero());
+}
+
+/// Staking mode that can be switched by the tests.
+pub struct MockStakingMode;
+
+impl MockStakingMode {
+    pub fn set(mode: StakingMode) {
+        STAKING_MODE.with(|m| *m.borrow_mut() = mode);
+    }
+}
+
+impl Get<StakingMode> for MockStakingMode {
+    fn get() -> StakingMode {
+        STAKING_MODE.with(|m| *m.borrow())
+    }
+}
+
+/// Vesting period that can be set by the tests.
+pub struct MockVestingPeriod;
+
+impl MockVestingPeriod {
+    pub fn set(period: BlockNumber) {
+        VESTING_PERIOD.with(|p| *p.borrow_mut() = period);
+    }
+}
+
+impl Get<BlockNumber> for MockVestingPeriod {
+    fn get() -> BlockNumber {
+        VESTING_PERIOD.with(|p| *p.borrow())
+    }
+}
+
+/// Demurrage rate that can be set by the tests.
+pub struct MockDemurrageRate;
+
+impl MockDemurrageRate {
+    pub fn set(rate: Perbill) {
+        DEMURRAGE_RATE.with(|r| *r.borrow_mut() = rate);
+    }
+}
+
+impl Get<Perbill> for MockDemurrageRate {
+    fn get() -> Perbill {
+        DEMURRAGE_RATE.with(|r| *r.borrow())
+    }
+}
+
+thread_local! {
+    static TRUST_LEVELS: RefCell<BTreeMap<AccountId, Vec<(AccountId, u32)>>> =
+        RefCell::new(BTreeMap::new());
+}
+
+/// Trust relationships that can be set by the tests.
+pub struct MockTrust;
+
+impl MockTrust {
+    pub fn set(who: AccountId, levels: Vec<(AccountId, u32)>) {
+        TRUST_LEVELS.with(|t| t.borrow_mut().insert(who, levels));
+    }
+}
+
+impl TrustBase<AccountId> for MockTrust {
+    fn remove_all_tmp() {}
+
+    fn get_trust_count(who: &AccountId) -> usize {
+        Self::get_trust_levels(who).len()
+    }
+
+    fn get_trust_count_old(who: &AccountId) -> usize {
+        Self::get_trust_count(who)
+    }
+
+    fn is_trust(who: &AccountId, target: &AccountId) -> bool {
+        Self::get_trust_levels(who)
+            .iter()
+            .any(|(trustee, _)| trustee == target)
+    }
+
+    fn is_trust_old(who: &AccountId, target: &AccountId) -> bool {
+        Self::is_trust(who, target)
+    }
+
+    fn get_trust_old(who: &AccountId) -> Vec<AccountId> {
+        Self::get_trust_levels(who)
+            .into_iter()
+            .map(|(trustee, _)| trustee)
+            .collect()
+    }
+
+    fn get_trust_levels(who: &AccountId) -> Vec<(AccountId, u32)> {
+        TRUST_LEVELS.with(|t| t.borrow().get(who).cloned().unwrap_or_default())
+    }
+
+    fn computed_path(_users: &[AccountId]) -> Result<(u32, u32), DispatchError> {
+        Err(DispatchError::Other("No paths in the mock"))
+    }
+
+    fn valid_nodes(_nodes: &[AccountId]) -> DispatchResult {
+        Ok(())
+    }
 }
 
 impl zd_tokens::Config for Test {
@@ -143,9 +262,19 @@ impl zd_tokens::Config for Test {
     type CurrencyId = CurrencyId;
     type WeightInfo = ();
     type Currency = Currencies;
-    type SocialPool = SocialPoolAccountId;
+    type ModuleId = ZdTokenModuleId;
+    type UpdateOrigin = frame_system::EnsureRoot<AccountId>;
+    type StakingMode = MockStakingMode;
+    type SolvencyCheckPeriod = SolvencyCheckPeriod;
+    type VestingPeriod = MockVestingPeriod;
+    type DemurrageRate = MockDemurrageRate;
+    type DemurragePeriod = DemurragePeriod;
+    type TrustBase = MockTrust;
+    type MaxTrustCount = MaxTrustCount;
     type Amount = Amount;
     type BaceToken = BaceToken;
+    type SocialCurrencies = SocialCurrencies;
+    type MaxBatch = MaxBatch;
 }
 
 pub struct ExtBuilder {
@@ -16,7 +16,13 @@
 
 use super::*;
 use crate::mock::{Event, *};
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{
+    assert_noop, assert_ok,
+    storage::migration::put_storage_value,
+    traits::{ExistenceRequirement, OnInitialize, WithdrawReasons},
+    StorageHasher,
+};
+use zd_primitives::AppId;
 
 fn new_test_ext() -> sp_io::TestExternalities {
     let mut ext = ExtBuilder::default().build();
@@ -34,17 +40,18 @@ macro_rules! transfer_social {
                     let to_old_free_balance = ZdToken::free_balance(&$value.1);
                     assert_ok!(ZdToken::transfer_social(
                         Origin::signed(ALICE),
+                        ORS,
                         $value.1,
                         $value.0
                     ));
 
                     assert_eq!(ZdToken::free_balance(&ALICE), from_old_free_balance - $value.0);
-                    assert_eq!(ZdToken::actual_balance(&ALICE), from_old_free_balance - $value.0);
+                    assert_eq!(ZdToken::actual_balance(ORS, &ALICE), from_old_free_balance - $value.0);
                     assert_eq!(ZdToken::free_balance(&$value.1), to_old_free_balance);
-                    assert_eq!(ZdToken::actual_balance(&$value.1), to_old_free_balance + $value.0);
-                    assert_eq!(ZdToken::social_balance(&$value.1), $value.0);
+                    assert_eq!(ZdToken::actual_balance(ORS, &$value.1), to_old_free_balance + $value.0);
+                    assert_eq!(ZdToken::social_balance(ORS, &$value.1), $value.0);
 
-                    let social_transferred_event = Event::zd_tokens(crate::Event::TransferSocial(ALICE, $value.1, $value.0));
+                    let social_transferred_event = Event::zd_tokens(crate::Event::TransferSocial(ORS, ALICE, $value.1, $value.0));
                     assert!(System::events().iter().any(|record| record.event == social_transferred_event));
                 });
             }
@@ -61,6 +68,190 @@ transfer_social! {
     transfer_social_5: (1, CHARLIE),
 }
 
+fn fund_pending(who: &AccountId, amount: u128) {
+    assert_ok!(Currencies::deposit(ORS, &ZdToken::social_pool(), amount));
+    ZdToken::set_pending_balance(ORS, who, amount);
+}
+
+#[test]
+fn transfer_social_batch_should_work() {
+    new_test_ext().execute_with(|| {
+        fund_pending(&ALICE, 50);
+        let old_balance = ZdToken::free_balance(&ALICE);
+
+        assert_ok!(ZdToken::transfer_social_batch(
+            Origin::signed(ALICE),
+            ORS,
+            vec![(BOB, 30), (CHARLIE, 40)]
+        ));
+
+        assert_eq!(ZdToken::pending_balance(ORS, &ALICE), 0);
+        assert_eq!(ZdToken::free_balance(&ALICE), old_balance - 20);
+        assert_eq!(ZdToken::social_balance(ORS, &BOB), 30);
+        assert_eq!(ZdToken::social_balance(ORS, &CHARLIE), 40);
+
+        for (to, amount) in [(BOB, 30), (CHARLIE, 40)].iter() {
+            let event = Event::zd_tokens(crate::Event::TransferSocial(ORS, ALICE, *to, *amount));
+            assert!(System::events().iter().any(|record| record.event == event));
+        }
+        assert_ok!(ZdToken::check_solvency());
+    });
+}
+
+#[test]
+fn transfer_social_batch_should_fail() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            ZdToken::transfer_social_batch(
+                Origin::signed(ALICE),
+                ORS,
+                vec![(BOB, u128::MAX), (CHARLIE, 1)]
+            ),
+            Error::<Test>::Overflow
+        );
+
+        assert_noop!(
+            ZdToken::transfer_social_batch(Origin::signed(CHARLIE), ORS, vec![(BOB, 10)]),
+            orml_tokens::Error::<Test>::BalanceTooLow
+        );
+
+        assert_noop!(
+            ZdToken::transfer_social_batch(
+                Origin::signed(ALICE),
+                ORS,
+                vec![(BOB, 1), (CHARLIE, 1), (BOB, 1), (CHARLIE, 1)]
+            ),
+            Error::<Test>::TooManyTransfers
+        );
+
+        ZdToken::set_social_balance(ORS, &BOB, u128::MAX);
+        assert_noop!(
+            ZdToken::transfer_social_batch(
+                Origin::signed(ALICE),
+                ORS,
+                vec![(CHARLIE, 10), (BOB, 1)]
+            ),
+            Error::<Test>::Overflow
+        );
+    });
+}
+
+#[test]
+fn transfer_social_to_trustees_should_work() {
+    new_test_ext().execute_with(|| {
+        MockTrust::set(ALICE, vec![(BOB, 10), (CHARLIE, 30), (4, 0)]);
+        fund_pending(&ALICE, 100);
+        let old_balance = ZdToken::free_balance(&ALICE);
+
+        assert_ok!(ZdToken::transfer_social_to_trustees(
+            Origin::signed(ALICE),
+            ORS,
+            100,
+            false
+        ));
+
+        assert_eq!(ZdToken::pending_balance(ORS, &ALICE), 1);
+        assert_eq!(ZdToken::social_balance(ORS, &BOB), 33);
+        assert_eq!(ZdToken::social_balance(ORS, &CHARLIE), 33);
+        assert_eq!(ZdToken::social_balance(ORS, &4), 33);
+
+        assert_ok!(ZdToken::transfer_social_to_trustees(
+            Origin::signed(ALICE),
+            ORS,
+            100,
+            true
+        ));
+
+        assert_eq!(ZdToken::pending_balance(ORS, &ALICE), 0);
+        assert_eq!(ZdToken::free_balance(&ALICE), old_balance - 99);
+        assert_eq!(ZdToken::social_balance(ORS, &BOB), 33 + 25);
+        assert_eq!(ZdToken::social_balance(ORS, &CHARLIE), 33 + 75);
+        assert_eq!(ZdToken::social_balance(ORS, &4), 33);
+        assert_ok!(ZdToken::check_solvency());
+    });
+}
+
+#[test]
+fn transfer_social_to_trustees_should_round_down() {
+    new_test_ext().execute_with(|| {
+        MockTrust::set(ALICE, vec![(BOB, 1), (CHARLIE, 1)]);
+        fund_pending(&ALICE, 101);
+
+        assert_ok!(ZdToken::transfer_social_to_trustees(
+            Origin::signed(ALICE),
+            ORS,
+            101,
+            true
+        ));
+
+        assert_eq!(ZdToken::pending_balance(ORS, &ALICE), 1);
+        assert_eq!(ZdToken::social_balance(ORS, &BOB), 50);
+        assert_eq!(ZdToken::social_balance(ORS, &CHARLIE), 50);
+        assert_ok!(ZdToken::check_solvency());
+    });
+}
+
+#[test]
+fn transfer_social_to_trustees_should_skip_zero_shares() {
+    new_test_ext().execute_with(|| {
+        MockTrust::set(ALICE, vec![(BOB, 1), (CHARLIE, 10), (4, 1)]);
+        fund_pending(&ALICE, 10);
+
+        assert_ok!(ZdToken::transfer_social_to_trustees(
+            Origin::signed(ALICE),
+            ORS,
+            2,
+            false
+        ));
+        assert_eq!(ZdToken::pending_balance(ORS, &ALICE), 10);
+
+        assert_ok!(ZdToken::transfer_social_to_trustees(
+            Origin::signed(ALICE),
+            ORS,
+            5,
+            true
+        ));
+        assert_eq!(ZdToken::pending_balance(ORS, &ALICE), 6);
+        assert_eq!(ZdToken::social_balance(ORS, &CHARLIE), 4);
+        let transferred = |to: AccountId| {
+            System::events().iter().any(|record| {
+                matches!(
+                    record.event,
+                    Event::zd_tokens(crate::Event::TransferSocial(_, _, dest, _)) if dest == to
+                )
+            })
+        };
+        assert!(transferred(CHARLIE));
+        assert!(!transferred(BOB));
+        assert!(!transferred(4));
+        assert_ok!(ZdToken::check_solvency());
+    });
+}
+
+#[test]
+fn transfer_social_to_trustees_should_fail() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            ZdToken::transfer_social_to_trustees(Origin::signed(ALICE), ORS, 100, false),
+            Error::<Test>::NoTrustees
+        );
+
+        MockTrust::set(ALICE, vec![(BOB, 0), (CHARLIE, 0)]);
+        assert_noop!(
+            ZdToken::transfer_social_to_trustees(Origin::signed(ALICE), ORS, 100, true),
+            Error::<Test>::NoTrustLevel
+        );
+        assert_ok!(ZdToken::transfer_social_to_trustees(
+            Origin::signed(ALICE),
+            ORS,
+            100,
+            false
+        ));
+        assert_eq!(ZdToken::social_balance(ORS, &BOB), 50);
+        assert_eq!(ZdToken::social_balance(ORS, &CHARLIE), 50);
+    });
+}
+
 #[test]
 fn staking_test() {
     new_test_ext().execute_with(|| {
@@ -69,13 +260,14 @@ fn staking_test() {
 
         assert_ok!(ZdToken::transfer_social(
             Origin::signed(ALICE),
+            ORS,
             CHARLIE,
             600
         ));
 
         let old_balance = ZdToken::free_balance(&CHARLIE);
 
-        <Accounts<Test>>::mutate(CHARLIE, |account| {
+        <SocialAccounts<Test>>::mutate(CHARLIE, ORS, |account| {
             account.pending = 599;
             account.social = 1;
         });
@@ -93,8 +285,8 @@ fn staking_test() {
 
         assert_eq!(ZdToken::total_staking(), 100 + 600);
         assert_eq!(ZdToken::free_balance(&CHARLIE), old_balance);
-        assert_eq!(ZdToken::pending_balance(&CHARLIE), 0);
-        assert_eq!(ZdToken::social_balance(&CHARLIE), 1);
+        assert_eq!(ZdToken::pending_balance(ORS, &CHARLIE), 0);
+        assert_eq!(ZdToken::social_balance(ORS, &CHARLIE), 1);
     });
 }
 
@@ -117,6 +309,128 @@ fn release_test() {
 
         assert_eq!(ZdToken::free_balance(&BOB), old_balance + 100);
         assert_eq!(ZdToken::total_staking(), 100 - 100);
+
+        let released_event = Event::zd_tokens(crate::Event::Released(BOB, 100));
+        assert!(System::events()
+            .iter()
+            .any(|record| record.event == released_event));
+    });
+}
+
+#[test]
+fn bonus_events_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(ZdToken::increase_bonus(&ALICE, &100));
+        assert_eq!(ZdToken::get_bonus_amount(), 100);
+        let increased_event = Event::zd_tokens(crate::Event::BonusIncreased(ALICE, 100));
+        assert!(System::events()
+            .iter()
+            .any(|record| record.event == increased_event));
+
+        assert_noop!(ZdToken::cut_bonus(&101), Error::<Test>::BonusTooLow);
+        assert_ok!(ZdToken::cut_bonus(&40));
+        assert_eq!(ZdToken::get_bonus_amount(), 60);
+        let cut_event = Event::zd_tokens(crate::Event::BonusCut(40));
+        assert!(System::events()
+            .iter()
+            .any(|record| record.event == cut_event));
+    });
+}
+
+#[test]
+fn staking_ledger_should_work() {
+    new_test_ext().execute_with(|| {
+        const APP_ID: AppId = *b"test    ";
+        let free_balance = ZdToken::free_balance(&ALICE);
+
+        assert_ok!(ZdToken::stake(&ALICE, &(APP_ID, BOB), &100));
+        assert_ok!(ZdToken::stake(&ALICE, &(APP_ID, BOB), &50));
+        assert_ok!(ZdToken::stake(&ALICE, &(APP_ID, CHARLIE), &10));
+        assert_eq!(ZdToken::free_balance(&ALICE), free_balance - 160);
+        assert_eq!(ZdToken::total_staking(), 160);
+        assert_eq!(ZdToken::staked(&ALICE, &(APP_ID, BOB)), 150);
+        let mut stakes = ZdToken::stakes_of(&ALICE);
+        stakes.sort();
+        assert_eq!(stakes, vec![((APP_ID, BOB), 150), ((APP_ID, CHARLIE), 10)]);
+
+        assert_noop!(
+            ZdToken::unstake(&ALICE, &(APP_ID, CHARLIE), &11),
+            Error::<Test>::StakeTooLow
+        );
+        assert_noop!(
+            ZdToken::unstake(&BOB, &(APP_ID, BOB), &1),
+            Error::<Test>::StakeTooLow
+        );
+        assert_ok!(ZdToken::unstake(&ALICE, &(APP_ID, CHARLIE), &10));
+        assert_eq!(ZdToken::stakes_of(&ALICE), vec![((APP_ID, BOB), 150)]);
+        assert_eq!(ZdToken::free_balance(&ALICE), free_balance - 150);
+        assert_eq!(ZdToken::total_staking(), 150);
+
+        assert_noop!(
+            ZdToken::move_stake(&ALICE, &BOB, &(APP_ID, BOB), &151),
+            Error::<Test>::StakeTooLow
+        );
+        assert_ok!(ZdToken::move_stake(&ALICE, &BOB, &(APP_ID, BOB), &100));
+        assert_eq!(ZdToken::staked(&ALICE, &(APP_ID, BOB)), 50);
+        assert_eq!(ZdToken::staked(&BOB, &(APP_ID, BOB)), 100);
+
+        // A forfeited stake stays in the pool, to be released to others.
+        assert_ok!(ZdToken::forfeit(&ALICE, &(APP_ID, BOB), &50));
+        assert!(ZdToken::stakes_of(&ALICE).is_empty());
+        assert_eq!(ZdToken::free_balance(&ALICE), free_balance - 150);
+        assert_eq!(ZdToken::total_staking(), 150);
+    });
+}
+
+#[test]
+fn reserve_staking_should_work() {
+    new_test_ext().execute_with(|| {
+        MockStakingMode::set(StakingMode::Reserve);
+        const APP_ID: AppId = *b"test    ";
+        let free_balance = ZdToken::free_balance(&ALICE);
+        let pool_balance = ZdToken::free_balance(&ZdToken::social_pool());
+
+        // Pending balance is used first.
+        assert_ok!(ZdToken::transfer_social(Origin::signed(ALICE), ORS, CHARLIE, 600));
+        ZdToken::set_pending_balance(ORS, &CHARLIE, 600);
+        <SocialAccounts<Test>>::mutate(CHARLIE, ORS, |account| account.social = 0);
+        assert_ok!(ZdToken::stake(&CHARLIE, &(APP_ID, BOB), &500));
+        assert_eq!(ZdToken::pending_balance(ORS, &CHARLIE), 100);
+        assert_eq!(Currencies::reserved_balance(ORS, &CHARLIE), 500);
+        assert!(ZdToken::stake(&CHARLIE, &(APP_ID, BOB), &101).is_err());
+
+        let pool_balance = pool_balance + 100;
+        assert_eq!(ZdToken::free_balance(&ZdToken::social_pool()), pool_balance);
+
+        // The funds stay on the account of the staker.
+        assert_ok!(ZdToken::stake(&ALICE, &(APP_ID, BOB), &300));
+        assert_eq!(ZdToken::free_balance(&ALICE), free_balance - 600 - 300);
+        assert_eq!(Currencies::reserved_balance(ORS, &ALICE), 300);
+        assert_eq!(ZdToken::total_staking(), 0);
+        assert_eq!(ZdToken::free_balance(&ZdToken::social_pool()), pool_balance);
+
+        assert_ok!(ZdToken::unstake(&ALICE, &(APP_ID, BOB), &100));
+        assert_eq!(ZdToken::free_balance(&ALICE), free_balance - 600 - 200);
+        assert_eq!(Currencies::reserved_balance(ORS, &ALICE), 200);
+
+        assert_ok!(ZdToken::move_stake(&ALICE, &BOB, &(APP_ID, BOB), &100));
+        assert_eq!(Currencies::reserved_balance(ORS, &ALICE), 100);
+        assert_eq!(Currencies::reserved_balance(ORS, &BOB), 100);
+        assert_eq!(ZdToken::staked(&BOB, &(APP_ID, BOB)), 100);
+
+        // Funds only move into the pool when the stake is forfeited.
+        assert_ok!(ZdToken::forfeit(&ALICE, &(APP_ID, BOB), &100));
+        assert_eq!(Currencies::reserved_balance(ORS, &ALICE), 0);
+        assert_eq!(ZdToken::total_staking(), 100);
+        assert_eq!(ZdToken::free_balance(&ZdToken::social_pool()), pool_balance + 100);
+        assert_ok!(ZdToken::release(&CHARLIE, &100));
+
+        // The ledger must match the reserves.
+        assert_ok!(<Currencies as MultiReservableCurrency<_>>::unreserve(ORS, &BOB, 100));
+        assert_noop!(
+            ZdToken::unstake(&BOB, &(APP_ID, BOB), &100),
+            Error::<Test>::ReserveTooLow
+        );
     });
 }
 
@@ -130,43 +444,63 @@ macro_rules! share_test {
 
                     assert_ok!(ZdToken::transfer_social(
                         Origin::signed(ALICE),
+                        ORS,
                         CHARLIE,
                         total_social_balance
                     ));
 
                     let targets = (100u64..(len + 100)).collect::<Vec<AccountId>>();
 
-                    let total_share_amount = per_social_currency::PRE_SHARE.mul_floor(total_social_balance);
-                    let reserved_amount = per_social_currency::PRE_RESERVED.mul_floor(total_social_balance);
-                    let burn_amount = per_social_currency::PRE_BURN.mul_floor(total_social_balance);
-                    let fee_amount = per_social_currency::PRE_FEE.mul_floor(total_social_balance);
+                    let ratios = ZdToken::social_ratios();
+                    let total_share_amount = ratios.share.mul_floor(total_social_balance);
+                    let reserved_amount = ratios.reserved.mul_floor(total_social_balance);
+                    let burn_amount = ratios.burn.mul_floor(total_social_balance);
+                    let fee_amount = ratios.fee.mul_floor(total_social_balance);
 
                     let pre_reward =
                         total_social_balance - total_share_amount - reserved_amount - burn_amount - fee_amount;
 
                     let old_total_issuance = <Currencies as MultiCurrency<_>>::total_issuance(BaceToken::get());
 
-                    assert_eq!(ZdToken::share(&CHARLIE, &targets[..]), fee_amount);
+                    assert_eq!(ZdToken::share(ORS, &CHARLIE, &targets[..]), fee_amount);
 
                     let count = targets.len() as u128;
 
                     let mut remaining_share: u128 = total_share_amount;
+                    let mut share_amount: u128 = 0;
                     if !(count == 0 || total_share_amount < count) {
-                        let share_amount =
-                            total_share_amount / count.max(per_social_currency::MIN_TRUST_COUNT as u128);
+                        share_amount =
+                            total_share_amount / count.max(ratios.min_trust_count as u128);
                         for target in targets {
-                            assert_eq!(ZdToken::social_balance(&target), share_amount);
+                            assert_eq!(ZdToken::social_balance(ORS, &target), share_amount);
                         }
                         remaining_share = total_share_amount - share_amount * count;
                     }
                     // println!("pre_reward: {:?}",pre_reward);
                     assert_eq!(ZdToken::get_bonus_amount(), pre_reward);
-                    assert_eq!(ZdToken::social_balance(&CHARLIE), remaining_share);
+                    assert_eq!(ZdToken::total_staking(), fee_amount + pre_reward);
+                    assert_ok!(ZdToken::check_solvency());
+                    assert_eq!(ZdToken::social_balance(ORS, &CHARLIE), remaining_share);
                     assert_eq!(
                         <Currencies as MultiCurrency<_>>::total_issuance(BaceToken::get()),
                         old_total_issuance - burn_amount
                     );
-                    assert_eq!(ZdToken::pending_balance(&CHARLIE), reserved_amount);
+                    assert_eq!(ZdToken::pending_balance(ORS, &CHARLIE), reserved_amount);
+
+                    let shared_event = Event::zd_tokens(crate::Event::Shared(
+                        ORS,
+                        CHARLIE,
+                        SharedAmounts {
+                            per_trustee: share_amount,
+                            trustees: len as u32,
+                            remaining: remaining_share,
+                            reserved: reserved_amount,
+                            burned: burn_amount,
+                            fee: fee_amount,
+                            bonus: pre_reward,
+                        },
+                    ));
+                    assert!(System::events().iter().any(|record| record.event == shared_event));
                 });
             }
         )*
@@ -186,27 +520,675 @@ fn claim_test() {
     new_test_ext().execute_with(|| {
         assert_ok!(ZdToken::staking(&ALICE, &100));
 
-        assert_ok!(ZdToken::claim(Origin::signed(CHARLIE)));
+        assert_ok!(ZdToken::claim(Origin::signed(CHARLIE), ORS));
 
-        <Accounts<Test>>::mutate(CHARLIE, |account| {
+        <SocialAccounts<Test>>::mutate(CHARLIE, ORS, |account| {
             account.pending = 91;
         });
 
         let old_balance = ZdToken::free_balance(&CHARLIE);
-        assert_ok!(ZdToken::claim(Origin::signed(CHARLIE)));
+        assert_ok!(ZdToken::claim(Origin::signed(CHARLIE), ORS));
 
         assert_eq!(ZdToken::free_balance(&CHARLIE), old_balance + 91);
-        assert_eq!(ZdToken::pending_balance(&CHARLIE), 0);
+        assert_eq!(ZdToken::pending_balance(ORS, &CHARLIE), 0);
 
-        assert_ok!(ZdToken::claim(Origin::signed(CHARLIE)));
+        assert_ok!(ZdToken::claim(Origin::signed(CHARLIE), ORS));
 
         assert_eq!(ZdToken::free_balance(&CHARLIE), old_balance + 91);
-        assert_eq!(ZdToken::pending_balance(&CHARLIE), 0);
+        assert_eq!(ZdToken::pending_balance(ORS, &CHARLIE), 0);
 
-        <Accounts<Test>>::mutate(CHARLIE, |account| {
+        <SocialAccounts<Test>>::mutate(CHARLIE, ORS, |account| {
             account.pending = 91;
         });
 
-        assert!(ZdToken::claim(Origin::signed(CHARLIE)).is_err());
+        assert!(ZdToken::claim(Origin::signed(CHARLIE), ORS).is_err());
+    });
+}
+
+#[test]
+fn vesting_should_work() {
+    new_test_ext().execute_with(|| {
+        MockVestingPeriod::set(100);
+        assert_ok!(ZdToken::transfer_social(
+            Origin::signed(ALICE),
+            ORS,
+            CHARLIE,
+            1000
+        ));
+        ZdToken::share(ORS, &CHARLIE, &[]);
+
+        assert_eq!(ZdToken::pending_balance(ORS, &CHARLIE), 100);
+        assert_eq!(ZdToken::locked_pending(ORS, &CHARLIE), 100);
+        assert_noop!(
+            ZdToken::claim(Origin::signed(CHARLIE), ORS),
+            Error::<Test>::PendingLocked
+        );
+
+        System::set_block_number(51);
+        assert_eq!(ZdToken::locked_pending(ORS, &CHARLIE), 50);
+        assert_ok!(ZdToken::claim_vested(Origin::signed(CHARLIE), ORS));
+        assert_eq!(ZdToken::free_balance(&CHARLIE), 50);
+        assert_eq!(ZdToken::pending_balance(ORS, &CHARLIE), 50);
+        let event = Event::zd_tokens(crate::Event::ClaimVested(ORS, CHARLIE, 50));
+        assert!(System::events().iter().any(|record| record.event == event));
+
+        // Only the unlocked part of `pending` can be paid with.
+        assert_ok!(ZdToken::staking(&CHARLIE, &10));
+        assert_eq!(ZdToken::free_balance(&CHARLIE), 40);
+        assert_eq!(ZdToken::pending_balance(ORS, &CHARLIE), 50);
+
+        System::set_block_number(101);
+        assert_eq!(ZdToken::locked_pending(ORS, &CHARLIE), 0);
+        assert_ok!(ZdToken::claim(Origin::signed(CHARLIE), ORS));
+        assert_eq!(ZdToken::free_balance(&CHARLIE), 90);
+        assert_eq!(ZdToken::pending_balance(ORS, &CHARLIE), 0);
+        assert!(ZdToken::vesting(CHARLIE, ORS).is_empty());
+        assert_ok!(ZdToken::check_solvency());
+    });
+}
+
+#[test]
+fn claim_to_should_work() {
+    new_test_ext().execute_with(|| {
+        fund_pending(&ALICE, 100);
+        let old_balance = ZdToken::free_balance(&BOB);
+
+        assert_ok!(ZdToken::claim_to(Origin::signed(ALICE), ORS, BOB, 30));
+        assert_eq!(ZdToken::free_balance(&BOB), old_balance + 30);
+        assert_eq!(ZdToken::pending_balance(ORS, &ALICE), 70);
+        let event = Event::zd_tokens(crate::Event::ClaimTo(ORS, ALICE, BOB, 30));
+        assert!(System::events().iter().any(|record| record.event == event));
+
+        assert_noop!(
+            ZdToken::claim_to(Origin::signed(ALICE), ORS, BOB, 71),
+            Error::<Test>::PendingTooLow
+        );
+        assert_noop!(
+            ZdToken::claim_to(Origin::signed(ALICE), ORS, BOB, u128::MAX),
+            Error::<Test>::PendingTooLow
+        );
+
+        orml_tokens::Accounts::<Test>::mutate(CHARLIE, ORS, |account| {
+            account.free = u128::MAX;
+        });
+        assert_noop!(
+            ZdToken::claim_to(Origin::signed(ALICE), ORS, CHARLIE, 1),
+            orml_tokens::Error::<Test>::BalanceOverflow
+        );
+
+        MockVestingPeriod::set(100);
+        ZdToken::vest(ORS, &ALICE, 50);
+        assert_ok!(ZdToken::claim_to(Origin::signed(ALICE), ORS, BOB, 20));
+        assert_noop!(
+            ZdToken::claim_to(Origin::signed(ALICE), ORS, BOB, 1),
+            Error::<Test>::PendingLocked
+        );
+        assert_eq!(ZdToken::free_balance(&BOB), old_balance + 50);
+        assert_eq!(ZdToken::pending_balance(ORS, &ALICE), 50);
+    });
+}
+
+#[test]
+fn vesting_schedules_should_merge() {
+    new_test_ext().execute_with(|| {
+        MockVestingPeriod::set(100);
+        ZdToken::vest(ORS, &CHARLIE, 100);
+
+        System::set_block_number(51);
+        ZdToken::vest(ORS, &CHARLIE, 100);
+        assert_eq!(
+            ZdToken::vesting(CHARLIE, ORS),
+            vec![
+                VestingSchedule {
+                    start: 1,
+                    locked: 100
+                },
+                VestingSchedule {
+                    start: 51,
+                    locked: 100
+                },
+            ]
+        );
+        assert_eq!(ZdToken::locked_pending(ORS, &CHARLIE), 150);
+
+        // The first tranche has vested, the second is halfway.
+        System::set_block_number(101);
+        assert_eq!(ZdToken::locked_pending(ORS, &CHARLIE), 50);
+
+        // Vested tranches are dropped, and the latest takes what is past the bound.
+        for _ in 0..MAX_VESTING_TRANCHES {
+            System::set_block_number(System::block_number() + 1);
+            ZdToken::vest(ORS, &CHARLIE, 10);
+        }
+        let schedules = ZdToken::vesting(CHARLIE, ORS);
+        assert_eq!(schedules.len(), MAX_VESTING_TRANCHES);
+        assert_eq!(schedules[0].start, 51);
+        assert_eq!(
+            schedules.last(),
+            Some(&VestingSchedule {
+                start: 101 + MAX_VESTING_TRANCHES as u64 - 1,
+                locked: 20
+            })
+        );
+
+        MockVestingPeriod::set(0);
+        assert_eq!(ZdToken::locked_pending(ORS, &CHARLIE), 0);
     });
 }
+
+#[test]
+fn demurrage_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(ZdToken::transfer_social(
+            Origin::signed(ALICE),
+            ORS,
+            CHARLIE,
+            1000
+        ));
+        assert_eq!(ZdToken::last_touched(CHARLIE), None);
+
+        MockDemurrageRate::set(Perbill::from_percent(10));
+        assert_ok!(ZdToken::transfer_social(
+            Origin::signed(ALICE),
+            ORS,
+            CHARLIE,
+            0
+        ));
+        assert_eq!(ZdToken::last_touched(CHARLIE), Some(1));
+
+        System::set_block_number(25);
+        assert_ok!(ZdToken::transfer_social(
+            Origin::signed(ALICE),
+            ORS,
+            CHARLIE,
+            100
+        ));
+        assert_eq!(ZdToken::social_balance(ORS, &CHARLIE), 810 + 100);
+        assert_eq!(ZdToken::last_touched(CHARLIE), Some(21));
+        assert_eq!(ZdToken::get_bonus_amount(), 190);
+        let event = Event::zd_tokens(crate::Event::Demurrage(CHARLIE, 190));
+        assert!(System::events().iter().any(|record| record.event == event));
+
+        // Other currencies do not decay.
+        assert_ok!(ZdToken::transfer_social(
+            Origin::signed(ALICE),
+            DOT,
+            BOB,
+            1000
+        ));
+        System::set_block_number(31);
+        assert_ok!(ZdToken::transfer_social(Origin::signed(ALICE), DOT, BOB, 0));
+        assert_eq!(ZdToken::social_balance(DOT, &BOB), 1000);
+
+        // 910 decays to 819 before it is shared.
+        ZdToken::share(ORS, &CHARLIE, &[]);
+        assert_eq!(ZdToken::pending_balance(ORS, &CHARLIE), 81);
+        assert_eq!(ZdToken::get_bonus_amount(), 190 + 91 + 495);
+        assert_eq!(ZdToken::total_staking(), 190 + 91 + 81 + 495);
+        assert_ok!(ZdToken::check_solvency());
+    });
+}
+
+#[test]
+fn demurrage_should_start_when_funds_arrive() {
+    new_test_ext().execute_with(|| {
+        MockDemurrageRate::set(Perbill::from_percent(10));
+        assert_ok!(ZdToken::transfer_social(Origin::signed(ALICE), ORS, 4, 0));
+        assert_eq!(ZdToken::last_touched(4), Some(1));
+
+        // Funds arrive in the empty account in the middle of a period.
+        System::set_block_number(9);
+        assert_ok!(ZdToken::transfer_social(Origin::signed(ALICE), ORS, 4, 1000));
+        assert_eq!(ZdToken::last_touched(4), Some(9));
+
+        System::set_block_number(12);
+        assert_ok!(ZdToken::transfer_social(Origin::signed(ALICE), ORS, 4, 0));
+        assert_eq!(ZdToken::social_balance(ORS, &4), 1000);
+        assert_eq!(ZdToken::last_touched(4), Some(9));
+
+        System::set_block_number(19);
+        assert_ok!(ZdToken::transfer_social(Origin::signed(ALICE), ORS, 4, 0));
+        assert_eq!(ZdToken::social_balance(ORS, &4), 900);
+        assert_eq!(ZdToken::last_touched(4), Some(19));
+        assert_ok!(ZdToken::check_solvency());
+    });
+}
+
+#[test]
+fn fees_should_fund_bonus() {
+    new_test_ext().execute_with(|| {
+        let old_total_issuance = <Currencies as MultiCurrency<_>>::total_issuance(ORS);
+        let fee = OrsCurrency::withdraw(
+            &ALICE,
+            1000,
+            WithdrawReasons::FEE,
+            ExistenceRequirement::KeepAlive,
+        )
+        .unwrap();
+        FeesToBonus::on_unbalanced(fee);
+
+        assert_eq!(ZdToken::get_bonus_amount(), 200);
+        assert_eq!(ZdToken::total_staking(), 200);
+        assert_eq!(Currencies::free_balance(ORS, &ZdToken::social_pool()), 200);
+        assert_eq!(
+            <Currencies as MultiCurrency<_>>::total_issuance(ORS),
+            old_total_issuance - 800
+        );
+        let event = Event::zd_tokens(crate::Event::BonusFunded(200));
+        assert!(System::events().iter().any(|record| record.event == event));
+        assert_ok!(ZdToken::check_solvency());
+    });
+}
+
+#[test]
+fn set_social_ratios_should_work() {
+    new_test_ext().execute_with(|| {
+        let ratios = SocialRatios {
+            reserved: Perbill::from_percent(20),
+            share: Perbill::from_percent(30),
+            burn: Perbill::from_percent(0),
+            fee: Perbill::from_percent(50),
+            min_trust_count: 10,
+        };
+        assert_noop!(
+            ZdToken::set_social_ratios(Origin::signed(ALICE), ratios),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            ZdToken::set_social_ratios(
+                Origin::root(),
+                SocialRatios {
+                    burn: Perbill::from_percent(1),
+                    ..ratios
+                }
+            ),
+            Error::<Test>::InvalidRatios
+        );
+        assert_noop!(
+            ZdToken::set_social_ratios(
+                Origin::root(),
+                SocialRatios {
+                    min_trust_count: 0,
+                    ..ratios
+                }
+            ),
+            Error::<Test>::InvalidRatios
+        );
+        assert_ok!(ZdToken::set_social_ratios(Origin::root(), ratios));
+        let event = Event::zd_tokens(crate::Event::SocialRatiosSet(ratios));
+        assert!(System::events().iter().any(|record| record.event == event));
+
+        // Not in force before the next round.
+        assert_eq!(ZdToken::social_ratios(), SocialRatios::default());
+        ZdToken::start_round(1);
+        assert_eq!(ZdToken::social_ratios(), ratios);
+        assert_eq!(ZdToken::next_ratios(), None);
+        assert_eq!(ZdToken::ratios_at(1), Some(ratios));
+
+        ZdToken::start_round(2);
+        assert_eq!(ZdToken::ratios_at(2), Some(ratios));
+
+        assert_ok!(ZdToken::transfer_social(Origin::signed(ALICE), ORS, CHARLIE, 1000));
+        assert_eq!(ZdToken::share(ORS, &CHARLIE, &[BOB]), 500);
+        assert_eq!(ZdToken::pending_balance(ORS, &CHARLIE), 200);
+        assert_eq!(ZdToken::social_balance(ORS, &BOB), 30);
+        assert_eq!(ZdToken::get_bonus_amount(), 0);
+    });
+}
+
+#[test]
+fn share_should_keep_earlier_pending() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(ZdToken::set_social_ratios(
+            Origin::root(),
+            SocialRatios {
+                reserved: Perbill::from_percent(10),
+                share: Perbill::from_percent(30),
+                burn: Perbill::from_percent(10),
+                fee: Perbill::from_percent(10),
+                min_trust_count: 1,
+            }
+        ));
+        ZdToken::start_round(1);
+
+        assert_ok!(ZdToken::transfer_social(Origin::signed(ALICE), DOT, CHARLIE, 1000));
+        assert_eq!(ZdToken::share(DOT, &CHARLIE, &[BOB]), 0);
+        assert_eq!(ZdToken::pending_balance(DOT, &CHARLIE), 600);
+
+        // A second share adds to what the first one reserved.
+        assert_ok!(ZdToken::transfer_social(Origin::signed(ALICE), DOT, CHARLIE, 1000));
+        assert_eq!(ZdToken::share(DOT, &CHARLIE, &[BOB]), 0);
+        assert_eq!(ZdToken::pending_balance(DOT, &CHARLIE), 1200);
+        assert_ok!(ZdToken::check_solvency());
+    });
+}
+
+#[test]
+fn community_token_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(ZdToken::transfer_social(Origin::signed(ALICE), DOT, CHARLIE, 1000));
+        assert_ok!(ZdToken::transfer_social(Origin::signed(ALICE), ORS, CHARLIE, 100));
+        assert_eq!(ZdToken::social_balance(DOT, &CHARLIE), 1000);
+        assert_eq!(ZdToken::social_balance(ORS, &CHARLIE), 100);
+        let event = Event::zd_tokens(crate::Event::TransferSocial(DOT, ALICE, CHARLIE, 1000));
+        assert!(System::events().iter().any(|record| record.event == event));
+        let mut currencies = ZdToken::social_currencies(&CHARLIE);
+        currencies.sort();
+        assert_eq!(currencies, vec![ORS, DOT]);
+
+        // Only the social currencies chosen by the runtime can be sent.
+        assert_noop!(
+            ZdToken::transfer_social(Origin::signed(ALICE), BTC, CHARLIE, 1000),
+            Error::<Test>::NotSocialCurrency
+        );
+        assert_noop!(
+            ZdToken::transfer_social_batch(Origin::signed(ALICE), BTC, vec![(CHARLIE, 1000)]),
+            Error::<Test>::NotSocialCurrency
+        );
+
+        assert_ok!(ZdToken::set_social_ratios(
+            Origin::root(),
+            SocialRatios {
+                reserved: Perbill::from_percent(10),
+                share: Perbill::from_percent(30),
+                burn: Perbill::from_percent(10),
+                fee: Perbill::from_percent(10),
+                min_trust_count: 1,
+            }
+        ));
+        ZdToken::start_round(1);
+
+        // Only `BaceToken` funds staking, the fee and the bonus are reserved to the owner.
+        assert_eq!(ZdToken::share(DOT, &CHARLIE, &[BOB]), 0);
+        assert_eq!(ZdToken::total_staking(), 0);
+        assert_eq!(ZdToken::get_bonus_amount(), 0);
+        assert_eq!(ZdToken::social_balance(DOT, &BOB), 300);
+        assert_eq!(ZdToken::pending_balance(DOT, &CHARLIE), 100 + 100 + 400);
+        assert_eq!(ZdToken::social_balance(ORS, &CHARLIE), 100);
+        assert_eq!(ZdToken::social_currencies(&CHARLIE), vec![ORS]);
+        let shared_event = Event::zd_tokens(crate::Event::Shared(
+            DOT,
+            CHARLIE,
+            SharedAmounts {
+                per_trustee: 300,
+                trustees: 1,
+                remaining: 0,
+                reserved: 600,
+                burned: 100,
+                fee: 0,
+                bonus: 0,
+            },
+        ));
+        assert!(System::events()
+            .iter()
+            .any(|record| record.event == shared_event));
+
+        let free_balance = Currencies::free_balance(DOT, &CHARLIE);
+        assert_ok!(ZdToken::claim(Origin::signed(CHARLIE), DOT));
+        assert_eq!(Currencies::free_balance(DOT, &CHARLIE), free_balance + 600);
+        assert_eq!(ZdToken::pending_balance(DOT, &CHARLIE), 0);
+        assert_eq!(ZdToken::pending_balance(ORS, &CHARLIE), 0);
+        assert_ok!(ZdToken::check_solvency());
+    });
+}
+
+#[test]
+fn migrate_social_accounts_should_work() {
+    new_test_ext().execute_with(|| {
+        put_storage_value(
+            b"ZdToken",
+            b"Accounts",
+            &Twox64Concat::hash(&CHARLIE.encode()),
+            SocialAccount::<u128> { pending: 20, social: 30 },
+        );
+
+        migrations::migrate_social_accounts::<Test>();
+        assert_eq!(ZdToken::pending_balance(ORS, &CHARLIE), 20);
+        assert_eq!(ZdToken::social_balance(ORS, &CHARLIE), 30);
+        assert_eq!(ZdToken::social_balance(DOT, &CHARLIE), 0);
+
+        // Running it again changes nothing.
+        migrations::migrate_social_accounts::<Test>();
+        assert_eq!(ZdToken::social_balance(ORS, &CHARLIE), 30);
+    });
+}
+
+#[test]
+fn migrate_social_pool_should_work() {
+    new_test_ext().execute_with(|| {
+        const OLD_POOL: AccountId = 10000;
+        let pool = ZdToken::social_pool();
+        assert_ne!(pool, OLD_POOL);
+
+        assert_eq!(ZdToken::storage_version(), Releases::V1_0_0);
+
+        assert_ok!(<Currencies as MultiCurrency<_>>::transfer(ORS, &ALICE, &OLD_POOL, 1000));
+        <SocialAccounts<Test>>::insert(OLD_POOL, ORS, SocialAccount { pending: 20, social: 30 });
+
+        migrations::migrate_to_v2::<Test>(&OLD_POOL);
+
+        assert_eq!(ZdToken::free_balance(&OLD_POOL), 0);
+        assert_eq!(ZdToken::free_balance(&pool), 1000);
+        assert_eq!(ZdToken::storage_version(), Releases::V2_0_0);
+        // The social account of the old pool stays with its owner.
+        assert_eq!(ZdToken::pending_balance(ORS, &OLD_POOL), 20);
+        assert_eq!(ZdToken::social_balance(ORS, &OLD_POOL), 30);
+        assert_eq!(ZdToken::pending_balance(ORS, &pool), 0);
+
+        // Only runs once.
+        assert_ok!(<Currencies as MultiCurrency<_>>::transfer(ORS, &ALICE, &OLD_POOL, 100));
+        migrations::migrate_to_v2::<Test>(&OLD_POOL);
+        assert_eq!(ZdToken::free_balance(&OLD_POOL), 100);
+        assert_eq!(ZdToken::free_balance(&pool), 1000);
+    });
+}
+
+#[test]
+fn failed_pool_migration_should_be_retried() {
+    new_test_ext().execute_with(|| {
+        const OLD_POOL: AccountId = 10000;
+        let pool = ZdToken::social_pool();
+        assert_ok!(<Currencies as MultiCurrency<_>>::transfer(ORS, &ALICE, &OLD_POOL, 1000));
+        orml_tokens::Accounts::<Test>::mutate(pool, ORS, |account| {
+            account.free = u128::MAX;
+        });
+
+        migrations::migrate_to_v2::<Test>(&OLD_POOL);
+        assert_eq!(ZdToken::free_balance(&OLD_POOL), 1000);
+        assert_eq!(ZdToken::storage_version(), Releases::V1_0_0);
+
+        orml_tokens::Accounts::<Test>::mutate(pool, ORS, |account| {
+            account.free = 0;
+        });
+        migrations::migrate_to_v2::<Test>(&OLD_POOL);
+        assert_eq!(ZdToken::free_balance(&OLD_POOL), 0);
+        assert_eq!(ZdToken::free_balance(&pool), 1000);
+        assert_eq!(ZdToken::storage_version(), Releases::V2_0_0);
+    });
+}
+
+#[test]
+fn insolvent_pool_should_raise_alarm() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(ZdToken::transfer_social(Origin::signed(ALICE), ORS, CHARLIE, 1000));
+        assert_ok!(ZdToken::increase_bonus(&ALICE, &100));
+        assert_eq!(
+            ZdToken::pool_accounting(ORS),
+            PoolAccounting {
+                pool: 1100,
+                pending: 0,
+                social: 1000,
+                staking: 100,
+                bonus: 100,
+            }
+        );
+        assert_ok!(ZdToken::check_solvency());
+
+        assert_eq!(<Currencies as MultiCurrency<_>>::slash(ORS, &ZdToken::social_pool(), 1), 0);
+        let accounting = ZdToken::pool_accounting(ORS);
+        assert!(!accounting.is_solvent());
+        assert_eq!(ZdToken::check_solvency(), Err("The social pool is insolvent"));
+
+        // Only checked every `SolvencyCheckPeriod` blocks.
+        let event = Event::zd_tokens(crate::Event::Insolvent(ORS, accounting));
+        ZdToken::on_initialize(SolvencyCheckPeriod::get() + 1);
+        assert!(!System::events().iter().any(|record| record.event == event));
+        ZdToken::on_initialize(SolvencyCheckPeriod::get() * 2);
+        assert!(System::events().iter().any(|record| record.event == event));
+    });
+}
+
+#[test]
+fn running_totals_should_match_accounts() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(ZdToken::transfer_social(Origin::signed(ALICE), ORS, CHARLIE, 1000));
+        ZdToken::set_pending_balance(ORS, &BOB, 10);
+        ZdToken::set_pending_balance(ORS, &CHARLIE, 20);
+        ZdToken::set_pending_balance(ORS, &BOB, 5);
+        assert_eq!(ZdToken::total_pending(ORS), 25);
+        assert_eq!(ZdToken::total_social(ORS), 1000);
+
+        <SocialAccounts<Test>>::mutate(CHARLIE, ORS, |account| account.pending = 0);
+        assert_eq!(
+            ZdToken::check_solvency(),
+            Err("Running totals do not match the social accounts")
+        );
+
+        migrations::init_social_totals::<Test>();
+        assert_eq!(ZdToken::total_pending(ORS), 5);
+        assert_eq!(ZdToken::total_social(ORS), 1000);
+    });
+}
+
+#[test]
+fn migrate_to_v2_should_stake_shared_bonus() {
+    new_test_ext().execute_with(|| {
+        const OLD_POOL: AccountId = 10000;
+        let pool = ZdToken::social_pool();
+        <SocialAccounts<Test>>::insert(CHARLIE, ORS, SocialAccount { pending: 20, social: 30 });
+        <TotalStaking<Test>>::put(100);
+        <Bonus<Test>>::put(70);
+        // Shares added 40 to the bonus without staking it.
+        assert_ok!(<Currencies as MultiCurrency<_>>::transfer(
+            ORS,
+            &ALICE,
+            &pool,
+            20 + 30 + 100 + 40
+        ));
+
+        migrations::migrate_to_v2::<Test>(&OLD_POOL);
+        assert_eq!(ZdToken::total_pending(ORS), 20);
+        assert_eq!(ZdToken::total_social(ORS), 30);
+        assert_eq!(ZdToken::total_staking(), 140);
+        assert_ok!(ZdToken::check_solvency());
+
+        // Only runs once.
+        <SocialAccounts<Test>>::insert(BOB, ORS, SocialAccount { pending: 1, social: 0 });
+        migrations::migrate_to_v2::<Test>(&OLD_POOL);
+        assert_eq!(ZdToken::total_pending(ORS), 20);
+        assert_eq!(ZdToken::total_staking(), 140);
+    });
+}
+
+#[test]
+fn stake_shared_bonus_should_be_bounded_by_bonus() {
+    new_test_ext().execute_with(|| {
+        <TotalStaking<Test>>::put(100);
+        <Bonus<Test>>::put(10);
+        assert_ok!(<Currencies as MultiCurrency<_>>::transfer(
+            ORS,
+            &ALICE,
+            &ZdToken::social_pool(),
+            200
+        ));
+
+        migrations::stake_shared_bonus::<Test>();
+        assert_eq!(ZdToken::total_staking(), 110);
+    });
+}
+
+/// Xorshift, so that the operation sequences are random but reproducible.
+struct Rng(u64);
+
+impl Rng {
+    fn below(&mut self, n: u64) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0 % n
+    }
+}
+
+/// Run a random operation on the pool, ignoring failures since they must not
+/// change anything.
+fn random_operation(rng: &mut Rng) {
+    const APP_ID: AppId = *b"test    ";
+    const ACTORS: [AccountId; 6] = [ALICE, BOB, CHARLIE, 100, 101, 102];
+    let pick = |rng: &mut Rng| ACTORS[rng.below(ACTORS.len() as u64) as usize];
+
+    let who = pick(rng);
+    let currency_id = [ORS, DOT][rng.below(2) as usize];
+    let amount = rng.below(1000) as u128;
+    let stake = ZdToken::stakes_of(&who).into_iter().next();
+    match rng.below(10) {
+        0 => {
+            let _ = ZdToken::transfer_social(Origin::signed(who), currency_id, pick(rng), amount);
+        }
+        1 => {
+            let targets = (0..rng.below(4)).map(|_| pick(rng)).collect::<Vec<_>>();
+            ZdToken::share(currency_id, &who, &targets[..]);
+        }
+        2 => {
+            let _ = ZdToken::increase_bonus(&who, &amount);
+        }
+        3 => {
+            let unbound = ZdToken::total_staking() - ZdToken::get_bonus();
+            let _ = ZdToken::release(&who, &amount.min(unbound));
+        }
+        4 => {
+            // Paying out the bonus, as seeds are harvested.
+            let amount = amount.min(ZdToken::get_bonus());
+            if ZdToken::release(&who, &amount).is_ok() {
+                assert_ok!(ZdToken::cut_bonus(&amount));
+            }
+        }
+        5 => {
+            let _ = ZdToken::claim(Origin::signed(who), currency_id);
+        }
+        6 => {
+            let _ = ZdToken::stake(&who, &(APP_ID, pick(rng)), &amount);
+        }
+        7 => {
+            if let Some((id, staked)) = stake {
+                let _ = ZdToken::unstake(&who, &id, &amount.min(staked));
+            }
+        }
+        8 => {
+            if let Some((id, staked)) = stake {
+                let _ = ZdToken::forfeit(&who, &id, &amount.min(staked));
+            }
+        }
+        _ => {
+            if let Some((id, staked)) = stake {
+                let _ = ZdToken::move_stake(&who, &pick(rng), &id, &amount.min(staked));
+            }
+        }
+    }
+}
+
+#[test]
+fn random_operations_should_keep_pool_solvent() {
+    for seed in 1..=20 {
+        new_test_ext().execute_with(|| {
+            MockStakingMode::set(match seed % 2 {
+                0 => StakingMode::Transfer,
+                _ => StakingMode::Reserve,
+            });
+            let mut rng = Rng(seed);
+            for _ in 0..200 {
+                random_operation(&mut rng);
+                assert_ok!(ZdToken::check_solvency());
+            }
+        });
+    }
+}
@@ -32,7 +32,12 @@ use sp_std::marker::PhantomData;
 /// Weight functions needed for zd_tokens.
 pub trait WeightInfo {
     fn transfer_social() -> Weight;
+    fn transfer_social_batch(n: u32, ) -> Weight;
+    fn transfer_social_to_trustees(n: u32, ) -> Weight;
     fn claim() -> Weight;
+    fn claim_vested() -> Weight;
+    fn claim_to() -> Weight;
+    fn set_social_ratios() -> Weight;
 }
 
 /// Weights for zd_tokens using the Substrate node and recommended hardware.
@@ -41,8 +46,26 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
     #[cfg(not(tarpaulin_include))]
     fn transfer_social() -> Weight {
         (104_100_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(11 as Weight))
+            .saturating_add(T::DbWeight::get().writes(10 as Weight))
+    }
+    #[cfg(not(tarpaulin_include))]
+    fn transfer_social_batch(n: u32, ) -> Weight {
+        (4_300_000 as Weight)
+            .saturating_add((104_800_000 as Weight).saturating_mul(n as Weight))
             .saturating_add(T::DbWeight::get().reads(4 as Weight))
-            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+            .saturating_add(T::DbWeight::get().reads((7 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes((6 as Weight).saturating_mul(n as Weight)))
+    }
+    #[cfg(not(tarpaulin_include))]
+    fn transfer_social_to_trustees(n: u32, ) -> Weight {
+        (9_800_000 as Weight)
+            .saturating_add((106_500_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().reads((8 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes((6 as Weight).saturating_mul(n as Weight)))
     }
     #[cfg(not(tarpaulin_include))]
     fn claim() -> Weight {
@@ -50,6 +73,23 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(3 as Weight))
             .saturating_add(T::DbWeight::get().writes(3 as Weight))
     }
+    #[cfg(not(tarpaulin_include))]
+    fn claim_vested() -> Weight {
+        (101_200_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+    }
+    #[cfg(not(tarpaulin_include))]
+    fn claim_to() -> Weight {
+        (104_700_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().writes(5 as Weight))
+    }
+    #[cfg(not(tarpaulin_include))]
+    fn set_social_ratios() -> Weight {
+        (21_300_000 as Weight)
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
 }
 
 // For backwards compatibility and tests
@@ -57,8 +97,26 @@ impl WeightInfo for () {
     #[cfg(not(tarpaulin_include))]
     fn transfer_social() -> Weight {
         (104_100_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(11 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(10 as Weight))
+    }
+    #[cfg(not(tarpaulin_include))]
+    fn transfer_social_batch(n: u32, ) -> Weight {
+        (4_300_000 as Weight)
+            .saturating_add((104_800_000 as Weight).saturating_mul(n as Weight))
             .saturating_add(RocksDbWeight::get().reads(4 as Weight))
-            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((7 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((6 as Weight).saturating_mul(n as Weight)))
+    }
+    #[cfg(not(tarpaulin_include))]
+    fn transfer_social_to_trustees(n: u32, ) -> Weight {
+        (9_800_000 as Weight)
+            .saturating_add((106_500_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((8 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((6 as Weight).saturating_mul(n as Weight)))
     }
     #[cfg(not(tarpaulin_include))]
     fn claim() -> Weight {
@@ -66,4 +124,21 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(3 as Weight))
             .saturating_add(RocksDbWeight::get().writes(3 as Weight))
     }
+    #[cfg(not(tarpaulin_include))]
+    fn claim_vested() -> Weight {
+        (101_200_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+    }
+    #[cfg(not(tarpaulin_include))]
+    fn claim_to() -> Weight {
+        (104_700_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(5 as Weight))
+    }
+    #[cfg(not(tarpaulin_include))]
+    fn set_social_ratios() -> Weight {
+        (21_300_000 as Weight)
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
 }
@@ -256,6 +256,19 @@ impl<T: Config> TrustBase<T::AccountId> for Pallet<T> {
         trusted_user.0
     }
 
+    fn get_trust_levels(who: &T::AccountId) -> Vec<(T::AccountId, u32)> {
+        Self::trust_list(&who)
+            .0
+            .into_iter()
+            .map(|target| {
+                let level = T::Reputation::get_reputation(&target)
+                    .and_then(|ir| ir.computed())
+                    .unwrap_or(0);
+                (target, level)
+            })
+            .collect()
+    }
+
     fn computed_path(users: &[T::AccountId]) -> Result<(u32, u32), DispatchError> {
         ensure!(T::SeedsBase::is_seed(&users[0]), Error::<T>::NotSeed);
         let mut start_ir = INIT_SEED_RANK;
@@ -263,7 +276,10 @@ impl<T: Config> TrustBase<T::AccountId> for Pallet<T> {
             .windows(2)
             .map(|u| -> Result<(u32, u32), Error<T>> {
                 if Self::is_trust(&u[0], &u[1]) {
-                    let end_ir = T::Reputation::get_reputation(&u[1]).unwrap_or(0);
+                    // Provisional reputation is not verified and does not count in paths.
+                    let end_ir = T::Reputation::get_reputation(&u[1])
+                        .and_then(|ir| ir.computed())
+                        .unwrap_or(0);
                     let item_dist = appro_ln(start_ir.saturating_sub(end_ir));
                     start_ir = end_ir;
                     let trust_count = Self::get_trust_count_old(&u[0]) as u32;
@@ -22,11 +22,13 @@ use frame_support::sp_runtime::{
 use frame_support::{construct_runtime, parameter_types, traits::GenesisBuild};
 
 pub use frame_system as system;
+use frame_system::EnsureRoot;
 use sp_core::H256;
 pub use sp_runtime::{Perbill, Permill};
 pub use zd_reputation;
 
 pub type AccountId = u64;
+pub type Balance = u128;
 pub type BlockNumber = u64;
 
 pub const ALICE: AccountId = 1;
@@ -50,6 +52,7 @@ construct_runtime!(
         UncheckedExtrinsic = UncheckedExtrinsic,
     {
         System: frame_system::{Module, Call, Config, Storage, Event<T>},
+        Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
         ZdReputation: zd_reputation::{Module, Call, Storage, Event<T>, Config<T>},
         ZdSeeds: zd_seeds::{Module, Call, Storage, Event<T>},
         ZdTrust: zd_trust::{Module, Call, Storage, Event<T>},
@@ -59,6 +62,10 @@ construct_runtime!(
 parameter_types! {
     pub const BlockHashCount: u64 = 250;
     pub const SS58Prefix: u8 = 42;
+    pub const ExistentialDeposit: u128 = 1;
+    pub const MaxLocks: u32 = 50;
+    pub const WatchDeposit: Balance = 100;
+    pub const MaxWatches: u32 = 2;
 }
 
 parameter_types! {
@@ -68,11 +75,39 @@ parameter_types! {
 
 impl zd_reputation::Config for Test {
     type Event = Event;
+    type UpdateOrigin = EnsureRoot<AccountId>;
+    type OnRoundAborted = ();
+    type OnThresholdCrossed = ();
+    type Currency = Balances;
+    type WatchDeposit = WatchDeposit;
+    type MaxWatches = MaxWatches;
+}
+
+impl pallet_balances::Config for Test {
+    type MaxLocks = MaxLocks;
+    type Balance = Balance;
+    type Event = Event;
+    type DustRemoval = ();
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+    type WeightInfo = pallet_balances::weights::SubstrateWeight<Test>;
+}
+
+parameter_types! {
+    pub const MaxSeeds: u32 = 500;
+    pub const MaxSeedTerms: u32 = 0;
+    pub const SeedCooldown: u32 = 0;
+    pub const MaxMissedHeartbeats: u32 = 0;
 }
 
 impl zd_seeds::Config for Test {
     type Event = Event;
     type Reputation = ZdReputation;
+    type UpdateOrigin = EnsureRoot<AccountId>;
+    type MaxSeeds = MaxSeeds;
+    type MaxSeedTerms = MaxSeedTerms;
+    type SeedCooldown = SeedCooldown;
+    type MaxMissedHeartbeats = MaxMissedHeartbeats;
 }
 
 parameter_types! {
@@ -111,7 +146,7 @@ impl system::Config for Test {
     type OnKilledAccount = ();
     type SystemWeightInfo = ();
     type SS58Prefix = SS58Prefix;
-    type AccountData = ();
+    type AccountData = pallet_balances::AccountData<Balance>;
 }
 
 pub struct ExtBuilder {
@@ -198,6 +198,21 @@ fn get_trust_old_should_work() {
     });
 }
 
+#[test]
+fn get_trust_levels_should_work() {
+    new_test_ext().execute_with(|| {
+        initialize_trust();
+        ZdReputation::mutate_reputation(&BOB, &30);
+
+        // (ALICE, CHARLIE), (ALICE, BOB)
+        assert_eq!(
+            ZdTrust::get_trust_levels(&ALICE),
+            vec![(BOB, 30), (CHARLIE, 0)]
+        );
+        assert!(ZdTrust::get_trust_levels(&EVE).is_empty());
+    });
+}
+
 #[test]
 fn trust_should_work() {
     new_test_ext().execute_with(|| {
@@ -0,0 +1,480 @@
+// Copyright 2021 ZeroDAO
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # ZdVoting Module
+//!
+//! ## Overview
+//!
+//! Referenda of `pallet_democracy` are weighted by tokens only. This module
+//! keeps a second tally for referenda that `ConfigOrigin` marks as weighted
+//! by reputation, or by tokens multiplied by reputation.
+//!
+//! The reputation used is the one finalised when the referendum was created,
+//! so a refresh during the referendum cannot move the outcome. The snapshot is
+//! taken in the block the referendum starts, and the round is pinned in
+//! `Reputation` until the referendum ends, so its scores stay stored and
+//! provable. Voters can also supply a `ReputationProof` against the reputation
+//! root of that round.
+//!
+//! At the start of the block before a weighted referendum ends, the weighted
+//! tally is checked and the referendum is cancelled if the ayes do not
+//! outweigh the nays. A weighted referendum is therefore only enacted if both
+//! the weighted tally and democracy's own tally approve it. A weighted
+//! referendum without any weighted vote is left to democracy's own tally.
+//!
+//! ## Interface
+//!
+//! ### Dispatchable Functions
+//!
+//! - `set_weighting` - Sets how votes on a referendum are weighted, requires
+//! `ConfigOrigin`.
+//! - `vote` - Votes on a weighted referendum.
+//! - `unlock` - Removes the vote on a finished referendum and unlocks tokens.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{
+    codec::{Decode, Encode},
+    dispatch::DispatchResultWithPostInfo,
+    ensure,
+    pallet_prelude::*,
+    traits::{Currency, LockIdentifier, LockableCurrency, WithdrawReasons},
+    transactional, RuntimeDebug,
+};
+use frame_system::{ensure_signed, pallet_prelude::*};
+use sp_runtime::{
+    traits::{One, SaturatedConversion, Saturating, Zero},
+    DispatchError,
+};
+use sp_std::{marker::PhantomData, vec::Vec};
+use zd_primitives::{merkle::ReputationProof, TIRStep};
+use zd_support::Reputation;
+
+pub use pallet::*;
+pub use pallet_democracy::ReferendumIndex;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+const VOTING_ID: LockIdentifier = *b"zdvoting";
+
+type BalanceOf<T> =
+    <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// How the votes on a referendum are weighted.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum VoteWeighting {
+    /// Token weighted, left to `pallet_democracy`.
+    Token,
+    /// Weighted by reputation.
+    Reputation,
+    /// Weighted by the locked tokens multiplied by reputation.
+    Blend,
+}
+
+impl Default for VoteWeighting {
+    fn default() -> Self {
+        VoteWeighting::Token
+    }
+}
+
+/// Weighted tally of a referendum.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Default, RuntimeDebug)]
+pub struct Tally {
+    /// Total weight of aye votes.
+    pub ayes: u128,
+
+    /// Total weight of nay votes.
+    pub nays: u128,
+}
+
+impl Tally {
+    fn add(&mut self, aye: bool, weight: u128) {
+        match aye {
+            true => self.ayes = self.ayes.saturating_add(weight),
+            false => self.nays = self.nays.saturating_add(weight),
+        }
+    }
+
+    fn remove(&mut self, aye: bool, weight: u128) {
+        match aye {
+            true => self.ayes = self.ayes.saturating_sub(weight),
+            false => self.nays = self.nays.saturating_sub(weight),
+        }
+    }
+}
+
+/// A vote on a weighted referendum.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+pub struct VoteRecord<Balance> {
+    /// Whether the vote is in favour.
+    pub aye: bool,
+
+    /// Weight of the vote in the tally.
+    pub weight: u128,
+
+    /// Tokens locked for the vote.
+    pub balance: Balance,
+}
+
+/// Access to the referenda of a governance module.
+pub trait Referenda<BlockNumber> {
+    /// Number of referenda created so far.
+    fn referendum_count() -> ReferendumIndex;
+
+    /// The block at which `index` ends, or `None` if it is not ongoing.
+    fn end_of(index: ReferendumIndex) -> Option<BlockNumber>;
+
+    /// Cancels the ongoing referendum `index`.
+    fn cancel(index: ReferendumIndex);
+}
+
+/// `Referenda` of `pallet_democracy`.
+pub struct DemocracyReferenda<T>(PhantomData<T>);
+
+impl<T: pallet_democracy::Config> Referenda<T::BlockNumber> for DemocracyReferenda<T> {
+    fn referendum_count() -> ReferendumIndex {
+        pallet_democracy::Module::<T>::referendum_count()
+    }
+
+    fn end_of(index: ReferendumIndex) -> Option<T::BlockNumber> {
+        pallet_democracy::Module::<T>::referendum_status(index)
+            .ok()
+            .map(|status| status.end)
+    }
+
+    fn cancel(index: ReferendumIndex) {
+        pallet_democracy::Module::<T>::internal_cancel_referendum(index)
+    }
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+        type Currency: LockableCurrency<Self::AccountId, Moment = Self::BlockNumber>;
+        type Reputation: Reputation<Self::AccountId, Self::BlockNumber, TIRStep>;
+        type Referenda: Referenda<Self::BlockNumber>;
+
+        /// The origin which may set the weighting of a referendum.
+        type ConfigOrigin: EnsureOrigin<Self::Origin>;
+    }
+
+    #[pallet::pallet]
+    #[pallet::generate_store(pub(super) trait Store)]
+    pub struct Pallet<T>(_);
+
+    /// The first referendum whose reputation has not been snapshot yet.
+    #[pallet::storage]
+    #[pallet::getter(fn next_referendum)]
+    pub type NextReferendum<T: Config> = StorageValue<_, ReferendumIndex, ValueQuery>;
+
+    /// Nonce of the reputation finalised when the referendum was created.
+    #[pallet::storage]
+    #[pallet::getter(fn snapshot)]
+    pub type Snapshots<T: Config> =
+        StorageMap<_, Twox64Concat, ReferendumIndex, u32, OptionQuery>;
+
+    /// Referenda with a snapshot that end at `BlockNumber`.
+    #[pallet::storage]
+    #[pallet::getter(fn ending_at)]
+    pub type EndingAt<T: Config> =
+        StorageMap<_, Twox64Concat, T::BlockNumber, Vec<ReferendumIndex>, ValueQuery>;
+
+    /// Weighting of ongoing weighted referenda.
+    #[pallet::storage]
+    #[pallet::getter(fn weighting)]
+    pub type Weightings<T: Config> =
+        StorageMap<_, Twox64Concat, ReferendumIndex, VoteWeighting, ValueQuery>;
+
+    /// Weighted tally of each referendum.
+    #[pallet::storage]
+    #[pallet::getter(fn tally)]
+    pub type Tallies<T: Config> = StorageMap<_, Twox64Concat, ReferendumIndex, Tally, ValueQuery>;
+
+    /// Votes of an account on weighted referenda.
+    #[pallet::storage]
+    #[pallet::getter(fn voting_of)]
+    pub type VotingOf<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        T::AccountId,
+        Twox64Concat,
+        ReferendumIndex,
+        VoteRecord<BalanceOf<T>>,
+        OptionQuery,
+    >;
+
+    #[pallet::event]
+    #[pallet::metadata(T::AccountId = "AccountId")]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// The weighting of a referendum is set. \[index, weighting\]
+        WeightingSet(ReferendumIndex, VoteWeighting),
+        /// An account voted on a weighted referendum. \[who, index, aye, weight\]
+        Voted(T::AccountId, ReferendumIndex, bool, u128),
+        /// A weighted referendum is tallied. \[index, ayes, nays, approved\]
+        Tallied(ReferendumIndex, u128, u128, bool),
+        /// An account removed its vote and unlocked tokens. \[who, index\]
+        Unlocked(T::AccountId, ReferendumIndex),
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// The referendum is not ongoing
+        ReferendumNotOngoing,
+        /// The referendum is still ongoing
+        ReferendumOngoing,
+        /// The referendum has no reputation snapshot
+        NoSnapshot,
+        /// Votes have already been cast on the referendum
+        VotingStarted,
+        /// The referendum is not weighted
+        NotWeighted,
+        /// No reputation in the snapshot round
+        NoReputation,
+        /// The reputation proof is invalid
+        InvalidProof,
+        /// Not enough free balance to lock
+        InsufficientBalance,
+        /// No vote on the referendum
+        NotVoter,
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(now: T::BlockNumber) -> Weight {
+            let count = Self::take_snapshots() as Weight;
+            let (ending, removed) = Self::tally_ending(now.saturating_add(One::one()));
+            let (ending, removed) = (ending as Weight, removed as Weight);
+            // The two reads of `on_finalize` are included.
+            T::DbWeight::get().reads_writes(
+                count
+                    .saturating_mul(2)
+                    .saturating_add(ending.saturating_mul(6))
+                    .saturating_add(5),
+                count
+                    .saturating_mul(3)
+                    .saturating_add(ending.saturating_mul(5))
+                    .saturating_add(removed)
+                    .saturating_add(2),
+            )
+        }
+
+        /// Snapshots the referenda started by the extrinsics of the block.
+        fn on_finalize(_now: T::BlockNumber) {
+            Self::take_snapshots();
+        }
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Sets the weighting of the ongoing referendum `index`, requires
+        /// `ConfigOrigin`.
+        ///
+        /// Can only be changed before any weighted vote has been cast.
+        #[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4,1))]
+        pub fn set_weighting(
+            origin: OriginFor<T>,
+            #[pallet::compact] index: ReferendumIndex,
+            weighting: VoteWeighting,
+        ) -> DispatchResultWithPostInfo {
+            T::ConfigOrigin::ensure_origin(origin)?;
+            Self::take_snapshots();
+            ensure!(
+                T::Referenda::end_of(index).is_some(),
+                Error::<T>::ReferendumNotOngoing
+            );
+            ensure!(
+                Snapshots::<T>::contains_key(index),
+                Error::<T>::NoSnapshot
+            );
+            ensure!(
+                !Tallies::<T>::contains_key(index),
+                Error::<T>::VotingStarted
+            );
+            match weighting {
+                VoteWeighting::Token => Weightings::<T>::remove(index),
+                _ => Weightings::<T>::insert(index, weighting),
+            }
+            Self::deposit_event(Event::WeightingSet(index, weighting));
+            Ok(().into())
+        }
+
+        /// Votes on the weighted referendum `index`, replacing any previous vote.
+        ///
+        /// The reputation is taken from the snapshot round of the referendum,
+        /// or from `proof` if it is given. For `Blend` referenda `balance` is
+        /// locked until the vote is removed with `unlock`.
+        #[pallet::weight(10_000 + T::DbWeight::get().reads_writes(6,4))]
+        #[transactional]
+        pub fn vote(
+            origin: OriginFor<T>,
+            #[pallet::compact] index: ReferendumIndex,
+            aye: bool,
+            balance: BalanceOf<T>,
+            proof: Option<ReputationProof>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let weighting = Self::weighting(index);
+            ensure!(weighting != VoteWeighting::Token, Error::<T>::NotWeighted);
+            ensure!(
+                T::Referenda::end_of(index).is_some(),
+                Error::<T>::ReferendumNotOngoing
+            );
+            let nonce = Self::snapshot(index).ok_or(Error::<T>::NoSnapshot)?;
+            let score = Self::snapshot_score(&who, nonce, proof)?;
+
+            let (weight, balance) = match weighting {
+                VoteWeighting::Blend => {
+                    ensure!(
+                        balance <= T::Currency::free_balance(&who),
+                        Error::<T>::InsufficientBalance
+                    );
+                    let weight = balance
+                        .saturated_into::<u128>()
+                        .saturating_mul(score as u128);
+                    (weight, balance)
+                }
+                _ => (score as u128, Zero::zero()),
+            };
+
+            Tallies::<T>::mutate(index, |tally| {
+                if let Some(old) = Self::voting_of(&who, index) {
+                    tally.remove(old.aye, old.weight);
+                }
+                tally.add(aye, weight);
+            });
+            VotingOf::<T>::insert(
+                &who,
+                index,
+                VoteRecord {
+                    aye,
+                    weight,
+                    balance,
+                },
+            );
+            if !balance.is_zero() {
+                T::Currency::extend_lock(VOTING_ID, &who, balance, WithdrawReasons::all());
+            }
+            Self::deposit_event(Event::Voted(who, index, aye, weight));
+            Ok(().into())
+        }
+
+        /// Removes the vote of the caller on the finished referendum `index` and
+        /// unlocks the tokens no longer needed by other votes.
+        #[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3,2))]
+        pub fn unlock(
+            origin: OriginFor<T>,
+            #[pallet::compact] index: ReferendumIndex,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(
+                T::Referenda::end_of(index).is_none(),
+                Error::<T>::ReferendumOngoing
+            );
+            ensure!(
+                VotingOf::<T>::contains_key(&who, index),
+                Error::<T>::NotVoter
+            );
+            VotingOf::<T>::remove(&who, index);
+            let locked = VotingOf::<T>::iter_prefix_values(&who)
+                .map(|record| record.balance)
+                .max()
+                .unwrap_or_else(Zero::zero);
+            match locked.is_zero() {
+                true => T::Currency::remove_lock(VOTING_ID, &who),
+                false => T::Currency::set_lock(VOTING_ID, &who, locked, WithdrawReasons::all()),
+            }
+            Self::deposit_event(Event::Unlocked(who, index));
+            Ok(().into())
+        }
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// Records and pins the finalised reputation nonce for every ongoing referendum
+    /// created since the last call. Returns the number of new referenda.
+    pub(crate) fn take_snapshots() -> u32 {
+        let count = T::Referenda::referendum_count();
+        let next = Self::next_referendum();
+        if next >= count {
+            return 0;
+        }
+        let nonce = T::Reputation::get_finalized_nonce();
+        // Referenda ending before the next tally are cleaned up by the one after.
+        let first_tally = frame_system::Module::<T>::block_number().saturating_add(2u32.into());
+        for index in next..count {
+            if let Some(end) = T::Referenda::end_of(index) {
+                Snapshots::<T>::insert(index, nonce);
+                T::Reputation::pin_round(nonce);
+                EndingAt::<T>::append(end.max(first_tally), index);
+            }
+        }
+        NextReferendum::<T>::put(count);
+        count - next
+    }
+
+    pub(crate) fn snapshot_score(
+        who: &T::AccountId,
+        nonce: u32,
+        proof: Option<ReputationProof>,
+    ) -> Result<u32, DispatchError> {
+        let score = match proof {
+            Some(proof) => {
+                ensure!(
+                    proof.nonce == nonce && T::Reputation::verify_reputation_proof(who, &proof),
+                    Error::<T>::InvalidProof
+                );
+                proof.score
+            }
+            None => T::Reputation::get_reputation_at(who, &nonce).unwrap_or_default(),
+        };
+        ensure!(score > 0, Error::<T>::NoReputation);
+        Ok(score)
+    }
+
+    /// Tallies the weighted referenda ending at `end`, cancelling the rejected
+    /// ones before democracy bakes them, and removes the snapshots and tallies of
+    /// all the referenda ending at `end`.
+    ///
+    /// Returns the number of referenda, and of the accounts of the snapshot rounds
+    /// no longer pinned.
+    pub(crate) fn tally_ending(end: T::BlockNumber) -> (u32, u32) {
+        let ending = EndingAt::<T>::take(end);
+        let mut removed = 0u32;
+        for index in ending.iter().copied() {
+            if let Some(nonce) = Snapshots::<T>::take(index) {
+                removed = removed.saturating_add(T::Reputation::unpin_round(nonce));
+            }
+            let weighting = Weightings::<T>::take(index);
+            let tally = Tallies::<T>::take(index);
+            if weighting != VoteWeighting::Token && T::Referenda::end_of(index).is_some() {
+                // Without weighted votes, the outcome is left to democracy.
+                let approved = tally == Tally::default() || tally.ayes > tally.nays;
+                if !approved {
+                    T::Referenda::cancel(index);
+                }
+                Self::deposit_event(Event::Tallied(index, tally.ayes, tally.nays, approved));
+            }
+        }
+        (ending.len() as u32, removed)
+    }
+}
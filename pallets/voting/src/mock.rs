@@ -0,0 +1,199 @@
+// Copyright 2021 ZeroDAO
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(test)]
+
+use crate as zd_voting;
+use crate::{ReferendumIndex, Referenda};
+use frame_support::sp_runtime::{
+    testing::Header,
+    traits::{BlakeTwo256, IdentityLookup},
+};
+use frame_support::{construct_runtime, parameter_types, traits::GenesisBuild};
+use frame_system as system;
+use frame_system::EnsureRoot;
+use sp_core::H256;
+use std::cell::RefCell;
+
+pub type AccountId = u64;
+pub type Balance = u128;
+pub type BlockNumber = u64;
+
+pub const ALICE: AccountId = 1;
+pub const BOB: AccountId = 2;
+pub const CHARLIE: AccountId = 3;
+
+pub const INIT_PERIOD: BlockNumber = 10;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+
+construct_runtime!(
+    pub enum Test where
+        Block = Block,
+        NodeBlock = Block,
+        UncheckedExtrinsic = UncheckedExtrinsic,
+    {
+        System: frame_system::{Module, Call, Config, Storage, Event<T>},
+        Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
+        ZdReputation: zd_reputation::{Module, Call, Storage, Event<T>, Config<T>},
+        ZdVoting: zd_voting::{Module, Call, Storage, Event<T>},
+    }
+);
+
+thread_local! {
+    static REFERENDA: RefCell<Vec<Option<BlockNumber>>> = RefCell::new(Vec::new());
+    static CANCELLED: RefCell<Vec<ReferendumIndex>> = RefCell::new(Vec::new());
+}
+
+/// Referenda kept in memory, ended by the tests.
+pub struct MockReferenda;
+
+impl MockReferenda {
+    pub fn start(end: BlockNumber) -> ReferendumIndex {
+        REFERENDA.with(|r| {
+            r.borrow_mut().push(Some(end));
+            r.borrow().len() as ReferendumIndex - 1
+        })
+    }
+
+    pub fn finish(index: ReferendumIndex) {
+        REFERENDA.with(|r| r.borrow_mut()[index as usize] = None);
+    }
+
+    pub fn cancelled() -> Vec<ReferendumIndex> {
+        CANCELLED.with(|c| c.borrow().clone())
+    }
+}
+
+impl Referenda<BlockNumber> for MockReferenda {
+    fn referendum_count() -> ReferendumIndex {
+        REFERENDA.with(|r| r.borrow().len() as ReferendumIndex)
+    }
+
+    fn end_of(index: ReferendumIndex) -> Option<BlockNumber> {
+        REFERENDA.with(|r| r.borrow().get(index as usize).cloned().flatten())
+    }
+
+    fn cancel(index: ReferendumIndex) {
+        Self::finish(index);
+        CANCELLED.with(|c| c.borrow_mut().push(index));
+    }
+}
+
+parameter_types! {
+    pub const BlockHashCount: u64 = 250;
+    pub const SS58Prefix: u8 = 42;
+    pub const ExistentialDeposit: u128 = 1;
+    pub const MaxLocks: u32 = 50;
+}
+
+impl zd_voting::Config for Test {
+    type Event = Event;
+    type Currency = Balances;
+    type Reputation = ZdReputation;
+    type Referenda = MockReferenda;
+    type ConfigOrigin = EnsureRoot<AccountId>;
+}
+
+parameter_types! {
+    pub const WatchDeposit: Balance = 100;
+    pub const MaxWatches: u32 = 2;
+}
+
+impl zd_reputation::Config for Test {
+    type Event = Event;
+    type UpdateOrigin = EnsureRoot<AccountId>;
+    type OnRoundAborted = ();
+    type OnThresholdCrossed = ();
+    type Currency = Balances;
+    type WatchDeposit = WatchDeposit;
+    type MaxWatches = MaxWatches;
+}
+
+impl pallet_balances::Config for Test {
+    type MaxLocks = MaxLocks;
+    type Balance = Balance;
+    type Event = Event;
+    type DustRemoval = ();
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+    type WeightInfo = pallet_balances::weights::SubstrateWeight<Test>;
+}
+
+impl system::Config for Test {
+    type BaseCallFilter = ();
+    type BlockWeights = ();
+    type BlockLength = ();
+    type DbWeight = ();
+    type Origin = Origin;
+    type Call = Call;
+    type Index = u64;
+    type BlockNumber = BlockNumber;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = AccountId;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type Event = Event;
+    type BlockHashCount = BlockHashCount;
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = SS58Prefix;
+    type AccountData = pallet_balances::AccountData<Balance>;
+}
+
+pub struct ExtBuilder {
+    period: BlockNumber,
+    balances: Vec<(AccountId, Balance)>,
+}
+
+impl Default for ExtBuilder {
+    fn default() -> Self {
+        Self {
+            period: INIT_PERIOD,
+            balances: vec![(ALICE, 1000), (BOB, 1000), (CHARLIE, 1000)],
+        }
+    }
+}
+
+impl ExtBuilder {
+    pub fn build(self) -> sp_io::TestExternalities {
+        let mut t = frame_system::GenesisConfig::default()
+            .build_storage::<Test>()
+            .unwrap();
+
+        zd_reputation::GenesisConfig::<Test> {
+            period: self.period,
+        }
+        .assimilate_storage(&mut t)
+        .unwrap();
+
+        pallet_balances::GenesisConfig::<Test> {
+            balances: self.balances,
+        }
+        .assimilate_storage(&mut t)
+        .unwrap();
+
+        REFERENDA.with(|r| r.borrow_mut().clear());
+        CANCELLED.with(|c| c.borrow_mut().clear());
+
+        t.into()
+    }
+}
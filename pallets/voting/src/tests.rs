@@ -0,0 +1,307 @@
+// Copyright 2021 ZeroDAO
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(test)]
+
+use super::*;
+use crate::mock::{Event, *};
+use frame_support::{
+    assert_noop, assert_ok,
+    traits::{OnFinalize, OnInitialize},
+};
+use sp_runtime::traits::BadOrigin;
+
+fn new_test_ext() -> sp_io::TestExternalities {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
+
+fn start_round(scores: &[(AccountId, u32)]) {
+    let next = ZdReputation::system_info().next;
+    System::set_block_number(System::block_number().max(next));
+    assert_ok!(ZdReputation::new_round());
+    for user_score in scores {
+        assert_ok!(ZdReputation::refresh_reputation(user_score));
+    }
+}
+
+fn finalise_round(scores: &[(AccountId, u32)]) {
+    start_round(scores);
+    ZdReputation::set_free();
+    ZdReputation::on_initialize(System::block_number());
+}
+
+fn weighted_referendum(weighting: VoteWeighting) -> ReferendumIndex {
+    let index = MockReferenda::start(System::block_number() + 20);
+    assert_ok!(ZdVoting::set_weighting(Origin::root(), index, weighting));
+    index
+}
+
+#[test]
+fn snapshot_should_work() {
+    new_test_ext().execute_with(|| {
+        finalise_round(&[(ALICE, 18)]);
+        let index = MockReferenda::start(30);
+        assert_eq!(ZdVoting::snapshot(index), None);
+        ZdVoting::on_initialize(System::block_number());
+        assert_eq!(ZdVoting::snapshot(index), Some(1));
+        assert_eq!(ZdVoting::next_referendum(), 1);
+        assert_eq!(ZdReputation::pins(1), 1);
+
+        // Referenda started by extrinsics are snapshot at the end of the block.
+        let index = MockReferenda::start(30);
+        ZdVoting::on_finalize(System::block_number());
+        assert_eq!(ZdVoting::snapshot(index), Some(1));
+        assert_eq!(ZdReputation::pins(1), 2);
+
+        // Referenda created while a round is refreshing use the last finalised one.
+        start_round(&[(ALICE, 30)]);
+        let index = MockReferenda::start(40);
+        assert_ok!(ZdVoting::set_weighting(
+            Origin::root(),
+            index,
+            VoteWeighting::Reputation
+        ));
+        assert_eq!(ZdVoting::snapshot(index), Some(1));
+    });
+}
+
+#[test]
+fn snapshots_should_be_removed() {
+    new_test_ext().execute_with(|| {
+        finalise_round(&[(ALICE, 18)]);
+        let token = MockReferenda::start(30);
+        let cancelled = MockReferenda::start(30);
+        ZdVoting::on_initialize(System::block_number());
+        assert_eq!(ZdVoting::ending_at(30), vec![token, cancelled]);
+
+        MockReferenda::finish(cancelled);
+        ZdVoting::on_initialize(29);
+        assert_eq!(ZdVoting::snapshot(token), None);
+        assert_eq!(ZdVoting::snapshot(cancelled), None);
+        assert_eq!(ZdReputation::pins(1), 0);
+        assert!(MockReferenda::cancelled().is_empty());
+    });
+}
+
+#[test]
+fn set_weighting_should_fail() {
+    new_test_ext().execute_with(|| {
+        finalise_round(&[(ALICE, 18)]);
+        assert_noop!(
+            ZdVoting::set_weighting(Origin::root(), 0, VoteWeighting::Reputation),
+            Error::<Test>::ReferendumNotOngoing
+        );
+        let index = MockReferenda::start(30);
+        assert_noop!(
+            ZdVoting::set_weighting(Origin::signed(ALICE), index, VoteWeighting::Reputation),
+            BadOrigin
+        );
+        assert_ok!(ZdVoting::set_weighting(
+            Origin::root(),
+            index,
+            VoteWeighting::Reputation
+        ));
+        assert_ok!(ZdVoting::vote(Origin::signed(ALICE), index, true, 0, None));
+        assert_noop!(
+            ZdVoting::set_weighting(Origin::root(), index, VoteWeighting::Token),
+            Error::<Test>::VotingStarted
+        );
+    });
+}
+
+#[test]
+fn vote_should_work() {
+    new_test_ext().execute_with(|| {
+        finalise_round(&[(ALICE, 18), (BOB, 7)]);
+        let index = weighted_referendum(VoteWeighting::Reputation);
+
+        // A refresh during the referendum does not change the weight.
+        finalise_round(&[(ALICE, 2), (BOB, 50)]);
+        assert_ok!(ZdVoting::vote(Origin::signed(ALICE), index, true, 0, None));
+        assert_ok!(ZdVoting::vote(Origin::signed(BOB), index, false, 0, None));
+        assert_eq!(ZdVoting::tally(index), Tally { ayes: 18, nays: 7 });
+
+        // Changing a vote moves its weight.
+        assert_ok!(ZdVoting::vote(Origin::signed(BOB), index, true, 0, None));
+        assert_eq!(ZdVoting::tally(index), Tally { ayes: 25, nays: 0 });
+
+        let voted_event = Event::zd_voting(crate::Event::Voted(BOB, index, true, 7));
+        assert!(System::events().iter().any(|record| record.event == voted_event));
+    });
+}
+
+#[test]
+fn vote_should_fail() {
+    new_test_ext().execute_with(|| {
+        finalise_round(&[(ALICE, 18)]);
+        let index = MockReferenda::start(30);
+        assert_noop!(
+            ZdVoting::vote(Origin::signed(ALICE), index, true, 0, None),
+            Error::<Test>::NotWeighted
+        );
+        assert_ok!(ZdVoting::set_weighting(
+            Origin::root(),
+            index,
+            VoteWeighting::Blend
+        ));
+        assert_noop!(
+            ZdVoting::vote(Origin::signed(CHARLIE), index, true, 10, None),
+            Error::<Test>::NoReputation
+        );
+        assert_noop!(
+            ZdVoting::vote(Origin::signed(ALICE), index, true, 1001, None),
+            Error::<Test>::InsufficientBalance
+        );
+        MockReferenda::finish(index);
+        assert_noop!(
+            ZdVoting::vote(Origin::signed(ALICE), index, true, 10, None),
+            Error::<Test>::ReferendumNotOngoing
+        );
+    });
+}
+
+#[test]
+fn vote_with_proof_should_work() {
+    new_test_ext().execute_with(|| {
+        finalise_round(&[(ALICE, 18), (BOB, 7)]);
+        let index = weighted_referendum(VoteWeighting::Reputation);
+
+        // The snapshot round can still be proven two rounds later.
+        finalise_round(&[(ALICE, 2)]);
+        finalise_round(&[(ALICE, 3)]);
+        assert_eq!(ZdReputation::reputation_proof_at(&ALICE, 4), None);
+        let proof = ZdReputation::reputation_proof_at(&ALICE, 1).unwrap();
+        assert_eq!(proof.nonce, 1);
+        assert_eq!(proof.score, 18);
+
+        let mut wrong_proof = proof.clone();
+        wrong_proof.score = 19;
+        assert_noop!(
+            ZdVoting::vote(Origin::signed(ALICE), index, true, 0, Some(wrong_proof)),
+            Error::<Test>::InvalidProof
+        );
+        assert_noop!(
+            ZdVoting::vote(Origin::signed(BOB), index, true, 0, Some(proof.clone())),
+            Error::<Test>::InvalidProof
+        );
+        assert_ok!(ZdVoting::vote(Origin::signed(ALICE), index, true, 0, Some(proof)));
+        assert_eq!(ZdVoting::tally(index), Tally { ayes: 18, nays: 0 });
+    });
+}
+
+#[test]
+fn snapshot_round_should_be_pinned() {
+    new_test_ext().execute_with(|| {
+        finalise_round(&[(ALICE, 18), (BOB, 7)]);
+        let index = weighted_referendum(VoteWeighting::Reputation);
+        let end = MockReferenda::end_of(index).unwrap();
+
+        finalise_round(&[(ALICE, 2)]);
+        finalise_round(&[(ALICE, 3)]);
+        assert_eq!(ZdReputation::round_accounts(1), vec![ALICE, BOB]);
+        assert_eq!(ZdReputation::pinned_score(1, ALICE), Some(18));
+        assert_ok!(ZdVoting::vote(Origin::signed(ALICE), index, true, 0, None));
+        assert_ok!(ZdVoting::vote(Origin::signed(BOB), index, false, 0, None));
+        assert_eq!(ZdVoting::tally(index), Tally { ayes: 18, nays: 7 });
+
+        // The round is removed once the referendum ends.
+        ZdVoting::on_initialize(end - 1);
+        assert_eq!(ZdReputation::pins(1), 0);
+        assert!(ZdReputation::round_accounts(1).is_empty());
+        assert_eq!(ZdReputation::pinned_score(1, ALICE), None);
+        assert!(!Tallies::<Test>::contains_key(index));
+    });
+}
+
+#[test]
+fn blend_vote_should_lock() {
+    new_test_ext().execute_with(|| {
+        finalise_round(&[(ALICE, 18), (BOB, 7)]);
+        let first = weighted_referendum(VoteWeighting::Blend);
+        let second = weighted_referendum(VoteWeighting::Blend);
+
+        assert_ok!(ZdVoting::vote(Origin::signed(ALICE), first, true, 100, None));
+        assert_ok!(ZdVoting::vote(Origin::signed(ALICE), second, false, 300, None));
+        assert_ok!(ZdVoting::vote(Origin::signed(BOB), first, false, 1000, None));
+        assert_eq!(ZdVoting::tally(first), Tally { ayes: 1800, nays: 7000 });
+        assert_eq!(Balances::locks(ALICE)[0].amount, 300);
+
+        assert_noop!(
+            ZdVoting::unlock(Origin::signed(ALICE), second),
+            Error::<Test>::ReferendumOngoing
+        );
+        MockReferenda::finish(second);
+        assert_noop!(
+            ZdVoting::unlock(Origin::signed(CHARLIE), second),
+            Error::<Test>::NotVoter
+        );
+        assert_ok!(ZdVoting::unlock(Origin::signed(ALICE), second));
+        assert_eq!(Balances::locks(ALICE)[0].amount, 100);
+
+        MockReferenda::finish(first);
+        assert_ok!(ZdVoting::unlock(Origin::signed(ALICE), first));
+        assert!(Balances::locks(ALICE).is_empty());
+        assert_eq!(ZdVoting::voting_of(ALICE, first), None);
+    });
+}
+
+#[test]
+fn tally_should_cancel_rejected() {
+    new_test_ext().execute_with(|| {
+        finalise_round(&[(ALICE, 18), (BOB, 7)]);
+        let rejected = weighted_referendum(VoteWeighting::Reputation);
+        let approved = weighted_referendum(VoteWeighting::Reputation);
+        let end = MockReferenda::end_of(rejected).unwrap();
+
+        assert_ok!(ZdVoting::vote(Origin::signed(BOB), rejected, true, 0, None));
+        assert_ok!(ZdVoting::vote(Origin::signed(ALICE), rejected, false, 0, None));
+        assert_ok!(ZdVoting::vote(Origin::signed(ALICE), approved, true, 0, None));
+
+        ZdVoting::on_initialize(end - 2);
+        assert!(MockReferenda::cancelled().is_empty());
+
+        ZdVoting::on_initialize(end - 1);
+        assert_eq!(MockReferenda::cancelled(), vec![rejected]);
+        assert_eq!(ZdVoting::weighting(rejected), VoteWeighting::Token);
+        assert_eq!(ZdVoting::weighting(approved), VoteWeighting::Token);
+        assert_eq!(ZdVoting::snapshot(rejected), None);
+        assert_eq!(ZdVoting::snapshot(approved), None);
+        assert!(ZdVoting::ending_at(end).is_empty());
+        assert!(!Tallies::<Test>::contains_key(rejected));
+        assert!(!Tallies::<Test>::contains_key(approved));
+
+        let rejected_event = Event::zd_voting(crate::Event::Tallied(rejected, 7, 18, false));
+        let approved_event = Event::zd_voting(crate::Event::Tallied(approved, 18, 0, true));
+        assert!(System::events().iter().any(|record| record.event == rejected_event));
+        assert!(System::events().iter().any(|record| record.event == approved_event));
+    });
+}
+
+#[test]
+fn tally_without_weighted_votes_should_defer() {
+    new_test_ext().execute_with(|| {
+        finalise_round(&[(ALICE, 18)]);
+        let index = weighted_referendum(VoteWeighting::Blend);
+        let end = MockReferenda::end_of(index).unwrap();
+
+        ZdVoting::on_initialize(end - 1);
+        assert!(MockReferenda::cancelled().is_empty());
+        assert_eq!(MockReferenda::end_of(index), Some(end));
+
+        let event = Event::zd_voting(crate::Event::Tallied(index, 0, 0, true));
+        assert!(System::events().iter().any(|record| record.event == event));
+    });
+}
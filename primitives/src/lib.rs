@@ -24,6 +24,8 @@ use sp_std::convert::TryInto;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 
+pub mod merkle;
+
 #[derive(Encode, Debug, Decode, Eq, PartialEq, Copy, Clone, PartialOrd, Ord)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum CurrencyId {
@@ -33,33 +35,47 @@ pub enum CurrencyId {
 
 pub type AppId = [u8; 8];
 
+/// What a stake is for: the application and its subject, e.g. a seed candidate
+/// or the payroll of a pathfinder.
+pub type StakingId<AccountId> = (AppId, AccountId);
+
 /// Balance of an account.
 pub type Balance = u128;
 
-pub const SWEEPER_PERIOD: u64 = 500;
-
-/// When other users receive their earnings, they receive that percentage of the earnings.
-pub const SWEEPER_PICKUP_RATIO: Perbill = Perbill::from_perthousand(20);
-
-pub mod per_social_currency {
-    use super::Perbill;
-
-    pub const MIN_TRUST_COUNT: u32 = 150;
-    /// Reserve the owner's free balance. The percentage can be adjusted by the community.
-    pub const PRE_RESERVED: Perbill = Perbill::from_percent(10);
+/// What the social pool holds, and what it holds it for.
+#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct PoolAccounting {
+    /// Free balance of the pool.
+    pub pool: Balance,
+    /// Sum of `pending` of all social accounts.
+    pub pending: Balance,
+    /// Sum of `social` of all social accounts.
+    pub social: Balance,
+    /// Total staked in the pool, including the bonus.
+    pub staking: Balance,
+    /// Part of `staking` set aside as the bonus.
+    pub bonus: Balance,
+}
 
-    /// Transfer to the social currency of users trusted by the owner.
-    pub const PRE_SHARE: Perbill = Perbill::from_percent(10);
+impl PoolAccounting {
+    /// What the pool owes.
+    pub fn liabilities(&self) -> Balance {
+        self.pending
+            .saturating_add(self.social)
+            .saturating_add(self.staking)
+    }
 
-    /// Share to all users.
-    pub const PRE_BURN: Perbill = Perbill::from_percent(10);
+    /// Whether the pool covers what it owes, and the bonus is covered by the staking.
+    pub fn is_solvent(&self) -> bool {
+        self.pool >= self.liabilities() && self.bonus <= self.staking
+    }
+}
 
-    /// Pathfinder's fee
-    pub const PRE_FEE: Perbill = Perbill::from_percent(10);
+pub const SWEEPER_PERIOD: u64 = 500;
 
-    // Used to solve the verifier's Dilemma and refresh seed.
-    // pub const PRE_REWARD: Perbill = Perbill::from_percent(10);
-}
+/// When other users receive their earnings, they receive that percentage of the earnings.
+pub const SWEEPER_PICKUP_RATIO: Perbill = Perbill::from_perthousand(20);
 
 /// The system is in the state of the algorithm.
 #[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug)]
@@ -76,6 +92,57 @@ impl Default for TIRStep {
     }
 }
 
+/// A reputation value and how it was obtained.
+#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum ReputationValue {
+    /// Computed by the reputation system and verified in a round.
+    Computed(u32),
+    /// Vouched for by reputable accounts, until the next round is finalised.
+    Provisional(u32),
+}
+
+impl ReputationValue {
+    /// The reputation value, whatever its kind.
+    pub fn score(&self) -> u32 {
+        match self {
+            ReputationValue::Computed(score) | ReputationValue::Provisional(score) => *score,
+        }
+    }
+
+    /// The reputation value, only if it was computed.
+    pub fn computed(&self) -> Option<u32> {
+        match self {
+            ReputationValue::Computed(score) => Some(*score),
+            ReputationValue::Provisional(_) => None,
+        }
+    }
+}
+
+/// How a seed was elected.
+#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum SeedOrigin {
+    /// Set by governance.
+    Governance,
+    /// Computed by a pathfinder and harvested in the seed step.
+    Computed,
+}
+
+/// Metadata of a seed.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct SeedInfo<AccountId> {
+    /// The round the seed was elected for.
+    pub nonce: u32,
+    /// Centrality score, zero if set by governance.
+    pub score: u64,
+    /// The pathfinder who submitted the seed, if computed.
+    pub pathfinder: Option<AccountId>,
+    /// How the seed was elected.
+    pub origin: SeedOrigin,
+}
+
 pub mod fee {
     use super::*;
 
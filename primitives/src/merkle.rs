@@ -0,0 +1,190 @@
+// Copyright 2021 ZeroDAO
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A binary Merkle tree over `(AccountId, score)` pairs.
+//!
+//! Leaves are hashed with `BlakeTwo256`, each pair of nodes is hashed as
+//! `hash(left ++ right)` and an unpaired node is promoted to the next level
+//! unchanged. The root of an empty tree is `H256::zero()`.
+
+use frame_support::{
+    codec::{Decode, Encode},
+    RuntimeDebug,
+};
+use sp_core::H256;
+use sp_runtime::traits::{BlakeTwo256, Hash};
+use sp_std::vec::Vec;
+
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+/// Proof that a leaf is included in a tree.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct MerkleProof {
+    /// Position of the leaf in the tree.
+    pub leaf_index: u32,
+
+    /// Total number of leaves.
+    pub leaf_count: u32,
+
+    /// Sibling hashes from the leaf up to the root.
+    pub items: Vec<H256>,
+}
+
+/// Proof of the reputation of an account in a finalised round.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ReputationProof {
+    /// The round in which the reputation was finalised.
+    pub nonce: u32,
+
+    /// Reputation value of the account in round `nonce`.
+    pub score: u32,
+
+    /// Proof of `leaf_hash(who, score)` under the root of round `nonce`.
+    pub proof: MerkleProof,
+}
+
+/// Hash of the leaf for `who` with a reputation of `score`.
+pub fn leaf_hash<AccountId: Encode>(who: &AccountId, score: u32) -> H256 {
+    BlakeTwo256::hash_of(&(who, score))
+}
+
+fn hash_node(left: &H256, right: &H256) -> H256 {
+    let mut data = left.as_bytes().to_vec();
+    data.extend_from_slice(right.as_bytes());
+    BlakeTwo256::hash(&data[..])
+}
+
+fn next_level(level: &[H256]) -> Vec<H256> {
+    level
+        .chunks(2)
+        .map(|pair| match pair {
+            [left, right] => hash_node(left, right),
+            _ => pair[0],
+        })
+        .collect()
+}
+
+/// Returns the root of the tree built from `leaves`.
+pub fn merkle_root(leaves: &[H256]) -> H256 {
+    if leaves.is_empty() {
+        return H256::zero();
+    }
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        level = next_level(&level[..]);
+    }
+    level[0]
+}
+
+/// Returns the inclusion proof of the leaf at `index`, or `None` if out of range.
+pub fn merkle_proof(leaves: &[H256], index: usize) -> Option<MerkleProof> {
+    if index >= leaves.len() {
+        return None;
+    }
+    let mut items = Vec::new();
+    let mut level = leaves.to_vec();
+    let mut position = index;
+    while level.len() > 1 {
+        let sibling = position ^ 1;
+        if sibling < level.len() {
+            items.push(level[sibling]);
+        }
+        level = next_level(&level[..]);
+        position /= 2;
+    }
+    Some(MerkleProof {
+        leaf_index: index as u32,
+        leaf_count: leaves.len() as u32,
+        items,
+    })
+}
+
+/// Whether `proof` proves that `leaf` is included under `root`.
+pub fn verify_proof(root: &H256, leaf: &H256, proof: &MerkleProof) -> bool {
+    if proof.leaf_index >= proof.leaf_count {
+        return false;
+    }
+    let mut items = proof.items.iter();
+    let mut computed = *leaf;
+    let mut position = proof.leaf_index as usize;
+    let mut width = proof.leaf_count as usize;
+    while width > 1 {
+        let sibling = position ^ 1;
+        if sibling < width {
+            let item = match items.next() {
+                Some(item) => item,
+                None => return false,
+            };
+            computed = match position % 2 {
+                0 => hash_node(&computed, item),
+                _ => hash_node(item, &computed),
+            };
+        }
+        position /= 2;
+        width = (width + 1) / 2;
+    }
+    items.next().is_none() && computed == *root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaves(count: u32) -> Vec<H256> {
+        (0..count).map(|i| leaf_hash(&i, i * 10)).collect()
+    }
+
+    #[test]
+    fn empty_root_is_zero() {
+        assert_eq!(merkle_root(&[]), H256::zero());
+        assert_eq!(merkle_proof(&[], 0), None);
+    }
+
+    #[test]
+    fn single_leaf_is_root() {
+        let leaves = leaves(1);
+        assert_eq!(merkle_root(&leaves[..]), leaves[0]);
+        let proof = merkle_proof(&leaves[..], 0).unwrap();
+        assert!(proof.items.is_empty());
+        assert!(verify_proof(&leaves[0], &leaves[0], &proof));
+    }
+
+    #[test]
+    fn proofs_should_verify() {
+        for count in 1..20u32 {
+            let leaves = leaves(count);
+            let root = merkle_root(&leaves[..]);
+            for (index, leaf) in leaves.iter().enumerate() {
+                let proof = merkle_proof(&leaves[..], index).unwrap();
+                assert!(verify_proof(&root, leaf, &proof));
+            }
+        }
+    }
+
+    #[test]
+    fn wrong_proofs_should_fail() {
+        let leaves = leaves(7);
+        let root = merkle_root(&leaves[..]);
+        let mut proof = merkle_proof(&leaves[..], 3).unwrap();
+        assert!(!verify_proof(&root, &leaves[2], &proof));
+        assert!(!verify_proof(&root, &leaf_hash(&3u32, 31), &proof));
+        proof.leaf_index = 2;
+        assert!(!verify_proof(&root, &leaves[3], &proof));
+        proof.leaf_index = 7;
+        assert!(!verify_proof(&root, &leaves[3], &proof));
+    }
+}
@@ -101,7 +101,7 @@ runtime_benchmarks! {
         for targer in 0..a {
             let targer_account: AccountId = account("targer", 0, targer);
             accounts.push((targer_account.clone(),100));
-            let _ = <ZdToken as MultiBaseToken<_,_>>::transfer_social(&vault.clone(), &targer_account.clone(), 10_000);
+            let _ = <ZdToken as MultiBaseToken<_,_>>::transfer_social(NATIVE, &vault.clone(), &targer_account.clone(), 10_000);
             for trustee in 1..MAX_TRUST_COUNT {
                 let trustee_account: AccountId = account("trustee", targer, trustee);
                 checked_trust(&targer_account,&trustee_account);
@@ -134,7 +134,7 @@ runtime_benchmarks! {
         init_challenge(&challenger,&targer,2);
         for s in 1..2 {
             let new_seed: AccountId = account("seed", 0, s);
-            <ZdSeeds as SeedsBase<_>>::add_seed(&new_seed);
+            <ZdSeeds as SeedsBase<_>>::add_seed(&new_seed)?;
         }
         let pathfinder: AccountId = account("pathfinder", 0, 0);
     }: _(RawOrigin::Signed(challenger.clone()),targer.clone(),pathfinder,1,2)
@@ -153,7 +153,7 @@ runtime_benchmarks! {
         for b in 1..a {
             let new_seed: AccountId = account("seed", 0, b);
             seeds.push(new_seed.clone());
-            <ZdSeeds as SeedsBase<_>>::add_seed(&new_seed);
+            <ZdSeeds as SeedsBase<_>>::add_seed(&new_seed)?;
             let nodes = (3..MAX_NODE_COUNT)
                 .map(|c|account("challenger", b, c))
                 .collect::<Vec<AccountId>>();
@@ -179,7 +179,7 @@ runtime_benchmarks! {
         init_challenge(&challenger,&targer,2);
         for s in 1..2 {
             let new_seed: AccountId = account("seed", 0, s);
-            <ZdSeeds as SeedsBase<_>>::add_seed(&new_seed);
+            <ZdSeeds as SeedsBase<_>>::add_seed(&new_seed)?;
         }
         let vault = account("vault", 0, 0);
         Currencies::deposit(NATIVE, &vault, 1_000_000_000_000u128)?;
@@ -201,7 +201,7 @@ runtime_benchmarks! {
 
         for b in 1..(a + 2) {
             let seed: AccountId = account("seed", 0, b);
-            <ZdSeeds as SeedsBase<_>>::add_seed(&seed);
+            <ZdSeeds as SeedsBase<_>>::add_seed(&seed)?;
             seeds.push(seed.clone());
             let first_node: AccountId = account("node", 0, 1);
             let mut nodes: Vec<AccountId> = vec![first_node.clone()];
@@ -1,7 +1,7 @@
 #[warn(unused_imports)]
 use crate::{
-    AccountId, Currencies, CurrencyId, GetNativeCurrencyId, MaxSeedCount, Runtime, System,
-    ZdRefreshSeeds, ZdTrust,
+    AccountId, Currencies, CurrencyId, GetNativeCurrencyId, MaxSeedCount, RefSeedTimeOut,
+    Runtime, System, ZdRefreshSeeds, ZdTrust,
 };
 use frame_benchmarking::{account, whitelisted_caller};
 use frame_system::RawOrigin;
@@ -473,6 +473,15 @@ runtime_benchmarks! {
         let sweeper: AccountId = account("sweeper", 0, 0);
     }: _(RawOrigin::Signed(sweeper.clone()),target.clone())
 
+    skip_seeds {
+        System::set_block_number(2000);
+
+        let caller: AccountId = whitelisted_caller();
+        ZdRefreshSeeds::start(RawOrigin::Signed(caller.clone()).into())?;
+
+        System::set_block_number(2000 + RefSeedTimeOut::get() + 1);
+    }: _(RawOrigin::Signed(caller.clone()))
+
 }
 
 #[cfg(test)]
@@ -1,10 +1,13 @@
-use crate::{AccountId, CurrencyId, Currencies, GetNativeCurrencyId, Runtime, ZdToken};
+use crate::{
+    AccountId, CurrencyId, Currencies, GetNativeCurrencyId, MaxBatch, MaxTrustCount, Runtime,
+    ZdReputation, ZdToken, ZdTrust,
+};
 use sp_std::prelude::*;
 use frame_system::RawOrigin;
 use frame_benchmarking::{account};
 use frame_support::assert_ok;
 
-use zd_support::MultiBaseToken;
+use zd_support::{MultiBaseToken, Reputation};
 
 use orml_benchmarking::runtime_benchmarks;
 use orml_traits::MultiCurrency;
@@ -21,15 +24,56 @@ runtime_benchmarks! {
         let from: AccountId = account("from", 0, SEED);
         let to: AccountId = account("to", 0, SEED);
 		Currencies::deposit(NATIVE, &from, 10_000)?;
-	}: _(RawOrigin::Signed(from.clone()), to.into(), 10_000)
+	}: _(RawOrigin::Signed(from.clone()), NATIVE, to.into(), 10_000)
+
+	transfer_social_batch {
+		let n in 1 .. MaxBatch::get();
+		let from: AccountId = account("from", 0, SEED);
+		Currencies::deposit(NATIVE, &from, 10_000 * n as u128)?;
+		let transfers = (0..n)
+			.map(|i| (account::<AccountId>("to", i, SEED).into(), 10_000u128))
+			.collect::<Vec<_>>();
+	}: _(RawOrigin::Signed(from.clone()), NATIVE, transfers)
+
+	transfer_social_to_trustees {
+		let n in 1 .. MaxTrustCount::get();
+		let from: AccountId = account("from", 0, SEED);
+		Currencies::deposit(NATIVE, &from, 10_000 * n as u128)?;
+		for i in 0..n {
+			let trustee: AccountId = account("trustee", i, SEED);
+			assert_ok!(ZdTrust::trust(RawOrigin::Signed(from.clone()).into(), trustee.clone()));
+			ZdReputation::mutate_reputation(&trustee, &1);
+		}
+	}: _(RawOrigin::Signed(from.clone()), NATIVE, 10_000 * n as u128, true)
 
 	claim {
 		let vault = account("vault", 0, 0);
         assert_ok!(Currencies::deposit(NATIVE, &vault, 1_000_000_000_000u128));
 		let _ = <ZdToken as MultiBaseToken<_,_>>::staking(&vault.clone(), &10_000u128);
 		let who: AccountId = account("who", 0, SEED);
-		ZdToken::set_pending_balance(&who.clone(), 10_000u128);
-	}: _(RawOrigin::Signed(who.clone()))
+		ZdToken::set_pending_balance(NATIVE, &who.clone(), 10_000u128);
+	}: _(RawOrigin::Signed(who.clone()), NATIVE)
+
+	claim_vested {
+		let vault = account("vault", 0, 0);
+		assert_ok!(Currencies::deposit(NATIVE, &vault, 1_000_000_000_000u128));
+		let _ = <ZdToken as MultiBaseToken<_,_>>::staking(&vault.clone(), &10_000u128);
+		let who: AccountId = account("who", 0, SEED);
+		ZdToken::set_pending_balance(NATIVE, &who.clone(), 10_000u128);
+	}: _(RawOrigin::Signed(who.clone()), NATIVE)
+
+	claim_to {
+		let vault = account("vault", 0, 0);
+		assert_ok!(Currencies::deposit(NATIVE, &vault, 1_000_000_000_000u128));
+		let _ = <ZdToken as MultiBaseToken<_,_>>::staking(&vault.clone(), &10_000u128);
+		let who: AccountId = account("who", 0, SEED);
+		let dest: AccountId = account("dest", 0, SEED);
+		ZdToken::set_pending_balance(NATIVE, &who.clone(), 10_000u128);
+	}: _(RawOrigin::Signed(who.clone()), NATIVE, dest.into(), 10_000u128)
+
+	set_social_ratios {
+		let ratios = zd_tokens::SocialRatios::default();
+	}: _(RawOrigin::Root, ratios)
 
 }
 
@@ -124,10 +124,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     spec_name: create_runtime_str!("ourspace"),
     impl_name: create_runtime_str!("ourspace"),
     authoring_version: 1,
-    spec_version: 100,
+    spec_version: 101,
     impl_version: 1,
     apis: RUNTIME_API_VERSIONS,
-    transaction_version: 1,
+    transaction_version: 2,
 };
 
 /// This determines the average expected block time that we are targeting.
@@ -293,7 +293,7 @@ parameter_types! {
 }
 
 impl pallet_transaction_payment::Config for Runtime {
-    type OnChargeTransaction = CurrencyAdapter<Balances, ()>;
+    type OnChargeTransaction = CurrencyAdapter<Balances, FeesToBonus>;
     type TransactionByteFee = TransactionByteFee;
     type WeightToFee = IdentityFee<Balance>;
     type FeeMultiplierUpdate = ();
@@ -332,13 +332,36 @@ impl orml_tokens::Config for Runtime {
     type OnDust = ();
 }
 
+parameter_types! {
+    pub const WatchDeposit: Balance = DOLLARS;
+    pub const MaxWatches: u32 = 8;
+}
+
 impl zd_reputation::Config for Runtime {
     type Event = Event;
+    type UpdateOrigin = MoreThanHalfCouncil;
+    type OnRoundAborted = (ZdRefreshSeeds, ZdRefreshReputation);
+    type OnThresholdCrossed = ();
+    type Currency = Balances;
+    type WatchDeposit = WatchDeposit;
+    type MaxWatches = MaxWatches;
+}
+
+parameter_types! {
+    pub const MaxSeeds: u32 = zd_reputation::MAX_SEED as u32;
+    pub const MaxSeedTerms: u32 = 12;
+    pub const SeedCooldown: u32 = 4;
+    pub const MaxMissedHeartbeats: u32 = 3;
 }
 
 impl zd_seeds::Config for Runtime {
     type Event = Event;
     type Reputation = ZdReputation;
+    type UpdateOrigin = MoreThanHalfCouncil;
+    type MaxSeeds = MaxSeeds;
+    type MaxSeedTerms = MaxSeedTerms;
+    type SeedCooldown = SeedCooldown;
+    type MaxMissedHeartbeats = MaxMissedHeartbeats;
 }
 
 parameter_types! {
@@ -356,7 +379,16 @@ impl zd_trust::Config for Runtime {
 }
 
 parameter_types! {
-    pub SocialPoolAccountId: AccountId = AccountId::from([0u8; 32]);
+    pub const ZdTokenModuleId: ModuleId = ModuleId(*b"zd/socpl");
+    pub const StakingMode: zd_tokens::StakingMode = zd_tokens::StakingMode::Transfer;
+    pub const SolvencyCheckPeriod: BlockNumber = HOURS;
+    pub const PendingVestingPeriod: BlockNumber = 0;
+    pub const DemurrageRate: Perbill = Perbill::from_percent(0);
+    pub const DemurragePeriod: BlockNumber = 30 * DAYS;
+    pub const FeesToBonusRatio: Perbill = Perbill::from_percent(20);
+    pub const TreasuryBurnToBonusRatio: Perbill = Perbill::from_percent(50);
+    pub const MaxBatch: u32 = 100;
+    pub SocialCurrencies: Vec<CurrencyId> = vec![CurrencyId::ORS, CurrencyId::SOCI];
 }
 
 impl zd_tokens::Config for Runtime {
@@ -364,11 +396,28 @@ impl zd_tokens::Config for Runtime {
     type CurrencyId = CurrencyId;
     type WeightInfo = ();
     type Currency = Currencies;
-    type SocialPool = SocialPoolAccountId;
+    type ModuleId = ZdTokenModuleId;
+    type UpdateOrigin = MoreThanHalfCouncil;
+    type StakingMode = StakingMode;
+    type SolvencyCheckPeriod = SolvencyCheckPeriod;
+    type VestingPeriod = PendingVestingPeriod;
+    type DemurrageRate = DemurrageRate;
+    type DemurragePeriod = DemurragePeriod;
+    type TrustBase = ZdTrust;
+    type MaxTrustCount = MaxTrustCount;
     type Amount = Amount;
     type BaceToken = GetNativeCurrencyId;
+    type SocialCurrencies = SocialCurrencies;
+    type MaxBatch = MaxBatch;
 }
 
+/// Part of the transaction fees goes to the bonus, the rest is burned.
+pub type FeesToBonus = zd_tokens::ToBonus<Runtime, Balances, FeesToBonusRatio, ()>;
+
+/// Part of what the treasury burns goes to the bonus.
+pub type TreasuryBurnToBonus =
+    zd_tokens::ToBonus<Runtime, Balances, TreasuryBurnToBonusRatio, ()>;
+
 parameter_types! {
     /// Response time period of challenge system.
     pub const ChallengeTimeout: BlockNumber = 100;
@@ -407,6 +456,7 @@ impl zd_refresh_reputation::Config for Runtime {
     type ChallengeBase = ZdChallenges;
     type SeedsBase = ZdSeeds;
     type RefRepuTiomeOut = RefRepuTiomeOut;
+    type MaxTrustCount = MaxTrustCount;
     type WeightInfo = ();
 }
 
@@ -415,6 +465,9 @@ parameter_types! {
     pub const SeedChallengeAmount: Balance = SEED_CHALLENGE_AMOUNT;
     pub const SeedReservStaking: Balance = SEED_RESERV_STAKING;
     pub const MaxSeedCount: u32 = 200;
+    pub const MaxCandidates: u32 = 1_000;
+    /// The seeds must be refreshed within this time period.
+    pub const RefSeedTimeOut: BlockNumber = 14_400;
 }
 
 impl zd_refresh_seeds::Config for Runtime {
@@ -426,12 +479,22 @@ impl zd_refresh_seeds::Config for Runtime {
     type MultiBaseToken = ZdToken;
     type SeedStakingAmount = SeedStakingAmount;
     type MaxSeedCount = MaxSeedCount;
+    type MaxCandidates = MaxCandidates;
     type ConfirmationPeriod = ConfirmationPeriod;
+    type RefSeedTimeOut = RefSeedTimeOut;
     type SeedChallengeAmount = SeedChallengeAmount;
     type SeedReservStaking = SeedReservStaking;
     type WeightInfo = ();
 }
 
+impl zd_voting::Config for Runtime {
+    type Event = Event;
+    type Currency = Balances;
+    type Reputation = ZdReputation;
+    type Referenda = zd_voting::DemocracyReferenda<Runtime>;
+    type ConfigOrigin = MoreThanHalfCouncil;
+}
+
 impl pallet_utility::Config for Runtime {
     type Call = Call;
     type Event = Event;
@@ -659,7 +722,7 @@ impl pallet_treasury::Config for Runtime {
     type SpendPeriod = SpendPeriod;
     type Burn = Burn;
     type OnSlash = Treasury;
-    type BurnDestination = ();
+    type BurnDestination = TreasuryBurnToBonus;
     type WeightInfo = ();
 }
 
@@ -948,12 +1011,13 @@ construct_runtime!(
         Currencies: orml_currencies::{Module, Storage, Event<T>},
 
         ZdReputation: zd_reputation::{Module, Call, Config<T>, Storage, Event<T>},
-        ZdSeeds: zd_seeds::{Module, Call, Storage, Event<T>},
+        ZdSeeds: zd_seeds::{Module, Call, Config<T>, Storage, Event<T>},
         ZdTrust: zd_trust::{Module, Call, Storage, Event<T>},
-        ZdToken: zd_tokens::{Module, Call, Storage, Event<T>},
+        ZdToken: zd_tokens::{Module, Call, Config, Storage, Event<T>},
         ZdChallenges: zd_challenges::{Module, Storage, Event<T>},
         ZdRefreshReputation: zd_refresh_reputation::{Module, Call, Storage, Event<T>},
         ZdRefreshSeeds: zd_refresh_seeds::{Module, Call, Storage, Event<T>},
+        ZdVoting: zd_voting::{Module, Call, Storage, Event<T>},
     }
 );
 
@@ -988,8 +1052,33 @@ pub type Executive = frame_executive::Executive<
     frame_system::ChainContext<Runtime>,
     Runtime,
     AllModules,
+    (MigrateSocialAccounts, MigrateZdTokensToV2),
 >;
 
+/// Keys the social accounts by currency.
+pub struct MigrateSocialAccounts;
+
+impl frame_support::traits::OnRuntimeUpgrade for MigrateSocialAccounts {
+    fn on_runtime_upgrade() -> Weight {
+        zd_tokens::migrations::migrate_social_accounts::<Runtime>()
+    }
+}
+
+/// Moves the social pool from the zero account to the account derived from
+/// `ZdTokenModuleId` and sets the running totals of the social accounts, once.
+pub struct MigrateZdTokensToV2;
+
+impl frame_support::traits::OnRuntimeUpgrade for MigrateZdTokensToV2 {
+    fn on_runtime_upgrade() -> Weight {
+        zd_tokens::migrations::migrate_to_v2::<Runtime>(&AccountId::from([0u8; 32]))
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn pre_upgrade() -> Result<(), &'static str> {
+        zd_tokens::migrations::pre_migrate_to_v2::<Runtime>(&AccountId::from([0u8; 32]))
+    }
+}
+
 impl_runtime_apis! {
     impl sp_api::Core<Block> for Runtime {
         fn version() -> RuntimeVersion {
@@ -1193,6 +1282,49 @@ impl_runtime_apis! {
         }
     }
 
+    impl zd_reputation_rpc_runtime_api::ReputationApi<Block, AccountId> for Runtime {
+        fn reputation_root(nonce: u32) -> Option<sp_core::H256> {
+            ZdReputation::reputation_root(nonce)
+        }
+
+        fn reputation_proof(who: AccountId) -> Option<zd_primitives::merkle::ReputationProof> {
+            ZdReputation::reputation_proof(&who)
+        }
+
+        fn reputation_proof_at(
+            who: AccountId,
+            nonce: u32,
+        ) -> Option<zd_primitives::merkle::ReputationProof> {
+            ZdReputation::reputation_proof_at(&who, nonce)
+        }
+
+        fn percentile(who: AccountId) -> Option<Permill> {
+            ZdReputation::percentile(&who)
+        }
+
+        fn leaderboard() -> Vec<(AccountId, u32)> {
+            ZdReputation::leaderboard(ZdReputation::finalized_nonce())
+        }
+    }
+
+    impl zd_tokens_rpc_runtime_api::TokensApi<Block, AccountId, CurrencyId> for Runtime {
+        fn stakes_of(who: AccountId) -> Vec<(zd_primitives::StakingId<AccountId>, Balance)> {
+            ZdToken::stakes_of(&who)
+        }
+
+        fn staked(who: AccountId, id: zd_primitives::StakingId<AccountId>) -> Balance {
+            ZdToken::staked_for(&who, &id)
+        }
+
+        fn social_pool() -> AccountId {
+            ZdToken::social_pool()
+        }
+
+        fn pool_accounting(currency_id: CurrencyId) -> zd_primitives::PoolAccounting {
+            ZdToken::pool_accounting(currency_id)
+        }
+    }
+
     #[cfg(feature = "runtime-benchmarks")]
     impl frame_benchmarking::Benchmark<Block> for Runtime {
         fn dispatch_benchmark(